@@ -0,0 +1,53 @@
+/// The C header of the solvers' FFI, kept next to the sources so C programs
+/// can include it. With the `ffi` feature it is generated into `OUT_DIR`,
+/// and only written here when `AOC_UPDATE_HEADER` is set.
+#[cfg(feature = "ffi")]
+const HEADER: &str = "include/adventofcode_2021.h";
+
+#[cfg(feature = "ffi")]
+const UPDATE_VAR: &str = "AOC_UPDATE_HEADER";
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    #[cfg(feature = "ffi")]
+    {
+        use std::{env, fs, path::Path};
+
+        println!("cargo:rerun-if-changed=src/days/ffi.rs");
+        println!("cargo:rerun-if-changed={}", HEADER);
+        println!("cargo:rerun-if-env-changed={}", UPDATE_VAR);
+        let crate_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+        let out_dir = env::var("OUT_DIR").unwrap();
+        let generated = Path::new(&out_dir).join("adventofcode_2021.h");
+        cbindgen::Builder::new()
+            .with_crate(&crate_dir)
+            .with_language(cbindgen::Language::C)
+            .with_include_guard("ADVENTOFCODE_2021_H")
+            .with_autogen_warning(
+                "/* Generated by build.rs with cbindgen; do not edit. */",
+            )
+            .with_include_version(false)
+            .with_cpp_compat(true)
+            // The plugin ABI is for plugins, not for callers of the library.
+            .exclude_item("ABI_VERSION")
+            .generate()
+            .expect("failed to generate the C header")
+            .write_to_file(&generated);
+
+        let header = Path::new(&crate_dir).join(HEADER);
+        let new = fs::read(&generated).unwrap();
+        if fs::read(&header).ok().as_ref() == Some(&new) {
+            return;
+        }
+        if env::var_os(UPDATE_VAR).is_some() {
+            fs::write(&header, new).expect("failed to write the C header");
+        } else {
+            println!(
+                "cargo:warning={} is out of date, update it with \
+                 `{}=1 cargo build --features ffi`",
+                HEADER, UPDATE_VAR
+            );
+        }
+    }
+}
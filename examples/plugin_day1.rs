@@ -0,0 +1,67 @@
+// A plugin solving day 1 with a single pass over the depths. Build it and
+// copy the library into the plugins directory:
+//
+//     cargo build --release --example plugin_day1
+//     mkdir -p plugins
+//     cp target/release/examples/libplugin_day1.so plugins/
+//
+// It then shows up in `list` and `all`, and runs with `plugin day1-one-pass`.
+
+use std::{
+    ffi::{c_char, CString},
+    slice, str,
+};
+
+use adventofcode_2021::plugin_abi::{
+    PluginAnswer, PluginDaySpec, PluginDays, ABI_VERSION,
+};
+
+static DAYS: [PluginDaySpec; 1] =
+    [PluginDaySpec { day: 1, name: c"day1-one-pass".as_ptr(), solve }];
+
+static PLUGIN: PluginDays = PluginDays {
+    abi_version: ABI_VERSION,
+    days: DAYS.as_ptr(),
+    len: DAYS.len(),
+    free_text,
+};
+
+#[no_mangle]
+pub extern "C" fn aoc_plugin_days() -> *const PluginDays {
+    &PLUGIN
+}
+
+unsafe extern "C" fn free_text(text: *mut c_char) {
+    drop(CString::from_raw(text));
+}
+
+unsafe extern "C" fn solve(
+    part: u32,
+    input: *const u8,
+    len: usize,
+) -> PluginAnswer {
+    let input = slice::from_raw_parts(input, len);
+    let (ok, text) = match count_increases(input, part) {
+        Ok(count) => (true, count.to_string()),
+        Err(e) => (false, e),
+    };
+    PluginAnswer { ok, text: CString::new(text).unwrap_or_default().into_raw() }
+}
+
+/// How often the sum of a window of depths grows, with windows of 1 depth
+/// in part 1 and 3 in part 2. Comparing two windows only needs the depths
+/// that differ, which are `window` apart.
+fn count_increases(input: &[u8], part: u32) -> Result<usize, String> {
+    let window = match part {
+        1 => 1,
+        2 => 3,
+        _ => return Err(format!("no such part: {}", part)),
+    };
+    let text = str::from_utf8(input).map_err(|e| e.to_string())?;
+    let depths = text
+        .lines()
+        .map(|line| line.trim().parse::<u32>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+    Ok(depths.windows(window + 1).filter(|w| w[window] > w[0]).count())
+}
@@ -0,0 +1,60 @@
+#ifndef ADVENTOFCODE_2021_H
+#define ADVENTOFCODE_2021_H
+
+/* Generated by build.rs with cbindgen; do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * The outcome of solving a part. Exactly one of `answer` and `error` is
+ * set. Release it with `aoc_result_free`.
+ */
+typedef struct AocResult {
+  /**
+   * The answer as the puzzle website takes it, such as the letters a
+   * day 13 picture reads as.
+   */
+  char *answer;
+  char *error;
+  /**
+   * Microseconds the part took, including reading the input.
+   */
+  double time_us;
+} AocResult;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Whether `day` has a solver.
+ */
+bool aoc_has_day(uint32_t day);
+
+/**
+ * Solve `part` (1 or 2) of `day` for the `len` bytes of UTF-8 text at
+ * `input`, which the call does not keep.
+ *
+ * # Safety
+ *
+ * `input` must point to `len` readable bytes.
+ */
+struct AocResult aoc_solve(uint32_t day, uint32_t part, const uint8_t *input, uintptr_t len);
+
+/**
+ * Release the strings of a result returned by `aoc_solve`.
+ *
+ * # Safety
+ *
+ * `result` must come from `aoc_solve` and not have been released yet.
+ */
+void aoc_result_free(struct AocResult result);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* ADVENTOFCODE_2021_H */
@@ -0,0 +1,170 @@
+use std::{
+    error::Error,
+    fs::File,
+    io::BufWriter,
+    path::{Path, PathBuf},
+};
+
+use super::Animation;
+use crate::grid::Grid;
+
+/// Small grids are scaled up until their longest side is at least this many
+/// pixels.
+const MIN_IMAGE_SIZE: usize = 400;
+
+/// Write each animation to an image file, picking the format from the
+/// extension of `path`: an animated `.gif`, or a `.png` that is animated when
+/// there is more than one frame. With several animations, each file name
+/// gets the animation's title appended.
+pub fn export(
+    animations: &[Animation],
+    path: &Path,
+    fps: f32,
+) -> Result<(), Box<dyn Error>> {
+    if animations.is_empty() {
+        return Err("no animations were recorded to export".into());
+    }
+
+    for animation in animations {
+        let path = if animations.len() == 1 {
+            path.to_path_buf()
+        } else {
+            titled_path(path, &animation.title)
+        };
+        let image = Image::new(animation);
+        let delay_ms = (1000.0 / fps.max(0.1)).round().min(65535.0) as u16;
+
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("gif") => image.write_gif(&path, delay_ms)?,
+            Some("png") => image.write_png(&path, delay_ms)?,
+            _ => {
+                return Err(format!(
+                    "cannot export {}: use a .gif or .png file",
+                    path.display()
+                )
+                .into())
+            }
+        }
+        println!("exported {} to {}", animation.title, path.display());
+    }
+    Ok(())
+}
+
+/// `dir/name.ext` becomes `dir/name-day-11-dumbo-octopus.ext`.
+fn titled_path(path: &Path, title: &str) -> PathBuf {
+    let mut slug = String::new();
+    for c in title.chars().filter(|c| c.is_ascii()) {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let mut name = format!("{}-{}", stem, slug.trim_end_matches('-'));
+    if let Some(ext) = path.extension() {
+        name.push('.');
+        name.push_str(&ext.to_string_lossy());
+    }
+    path.with_file_name(name)
+}
+
+/// The frames of an animation as palette indexes, all scaled and padded to
+/// the same size.
+struct Image {
+    width: usize,
+    height: usize,
+    palette: Vec<u8>,
+    frames: Vec<Vec<u8>>,
+}
+
+impl Image {
+    fn new(animation: &Animation) -> Self {
+        let cells = animation.frames.iter().map(|frame| &frame.cells);
+        let width = cells.clone().map(Grid::width).max().unwrap_or(0);
+        let height = cells.clone().map(Grid::height).max().unwrap_or(0);
+        let scale = (MIN_IMAGE_SIZE / width.max(height).max(1)).max(1);
+
+        let n_colors = animation.palette.len().min(256);
+        let palette = animation.palette[..n_colors]
+            .iter()
+            .flat_map(|swatch| <[u8; 3]>::from(swatch.color))
+            .collect();
+
+        let frames = cells
+            .map(|cells| {
+                let mut pixels =
+                    Vec::with_capacity(width * height * scale * scale);
+                for y in 0..height * scale {
+                    pixels.extend((0..width * scale).map(|x| {
+                        cells
+                            .get((x / scale, y / scale))
+                            .map_or(0, |&value| value % n_colors as u8)
+                    }));
+                }
+                pixels
+            })
+            .collect();
+
+        Self { width: width * scale, height: height * scale, palette, frames }
+    }
+
+    fn size(&self) -> Result<(u16, u16), Box<dyn Error>> {
+        match (u16::try_from(self.width), u16::try_from(self.height)) {
+            (Ok(width), Ok(height)) => Ok((width, height)),
+            _ => Err(format!(
+                "image of {}x{} pixels is too large",
+                self.width, self.height
+            )
+            .into()),
+        }
+    }
+
+    fn write_gif(
+        &self,
+        path: &Path,
+        delay_ms: u16,
+    ) -> Result<(), Box<dyn Error>> {
+        let (width, height) = self.size()?;
+        let file = BufWriter::new(File::create(path)?);
+        let mut encoder =
+            gif::Encoder::new(file, width, height, &self.palette)?;
+        encoder.set_repeat(gif::Repeat::Infinite)?;
+        for pixels in &self.frames {
+            let frame = gif::Frame {
+                width,
+                height,
+                delay: delay_ms.div_ceil(10),
+                buffer: pixels.into(),
+                ..gif::Frame::default()
+            };
+            encoder.write_frame(&frame)?;
+        }
+        Ok(())
+    }
+
+    fn write_png(
+        &self,
+        path: &Path,
+        delay_ms: u16,
+    ) -> Result<(), Box<dyn Error>> {
+        let file = BufWriter::new(File::create(path)?);
+        let mut encoder =
+            png::Encoder::new(file, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Indexed);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.set_palette(self.palette.as_slice());
+        if self.frames.len() > 1 {
+            encoder.set_animated(self.frames.len() as u32, 0)?;
+            encoder.set_frame_delay(delay_ms, 1000)?;
+        }
+
+        let mut writer = encoder.write_header()?;
+        for pixels in &self.frames {
+            writer.write_image_data(pixels)?;
+        }
+        writer.finish()?;
+        Ok(())
+    }
+}
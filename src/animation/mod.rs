@@ -0,0 +1,116 @@
+use std::{
+    error::Error,
+    mem,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
+
+use crate::grid::Grid;
+
+#[cfg(feature = "export")]
+mod export;
+#[cfg(feature = "export")]
+pub use self::export::export;
+
+mod terminal;
+#[cfg(feature = "tui")]
+pub use self::terminal::FullScreen;
+
+static RECORDING: AtomicBool = AtomicBool::new(false);
+static FINISHED: Mutex<Vec<Animation>> = Mutex::new(Vec::new());
+
+/// How cells with a given value are drawn: `palette[value]`.
+pub type Palette = &'static [Swatch];
+
+#[derive(Clone, Copy)]
+pub struct Swatch {
+    pub glyph: char,
+    pub color: (u8, u8, u8),
+}
+
+#[cfg(feature = "animation")]
+impl Swatch {
+    pub const fn new(glyph: char, color: (u8, u8, u8)) -> Self {
+        Self { glyph, color }
+    }
+}
+
+pub struct Frame {
+    pub cells: Grid<u8>,
+    pub caption: String,
+}
+
+#[cfg(feature = "animation")]
+impl Frame {
+    pub fn new(cells: Grid<u8>, caption: impl Into<String>) -> Self {
+        Self { cells, caption: caption.into() }
+    }
+}
+
+pub struct Animation {
+    pub title: String,
+    pub palette: Palette,
+    pub frames: Vec<Frame>,
+}
+
+/// Collects the frames of one animation while a day's part runs. It does
+/// nothing unless visualization was turned on with [`start_recording`].
+#[cfg(feature = "animation")]
+pub struct Recorder(Option<Animation>);
+
+#[cfg(feature = "animation")]
+impl Recorder {
+    pub fn new(title: impl Into<String>, palette: Palette) -> Self {
+        if RECORDING.load(Ordering::Relaxed) {
+            Self(Some(Animation {
+                title: title.into(),
+                palette,
+                frames: Vec::new(),
+            }))
+        } else {
+            Self(None)
+        }
+    }
+
+    /// Add a frame. `make_frame` is only called while recording.
+    #[inline]
+    pub fn frame(&mut self, make_frame: impl FnOnce() -> Frame) {
+        if let Some(animation) = &mut self.0 {
+            animation.frames.push(make_frame());
+        }
+    }
+}
+
+#[cfg(feature = "animation")]
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        if let Some(animation) = self.0.take() {
+            if !animation.frames.is_empty() {
+                FINISHED.lock().unwrap().push(animation);
+            }
+        }
+    }
+}
+
+pub fn start_recording() {
+    RECORDING.store(true, Ordering::Relaxed);
+}
+
+#[cfg(feature = "tui")]
+pub fn stop_recording() {
+    RECORDING.store(false, Ordering::Relaxed);
+}
+
+/// The animations recorded since the last call.
+pub fn take_recorded() -> Vec<Animation> {
+    mem::take(&mut *FINISHED.lock().unwrap())
+}
+
+pub fn play(animations: &[Animation], fps: f32) -> Result<(), Box<dyn Error>> {
+    for animation in animations {
+        terminal::play(animation, fps)?;
+    }
+    Ok(())
+}
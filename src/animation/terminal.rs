@@ -0,0 +1,247 @@
+use std::{
+    error::Error,
+    io::{self, Write},
+};
+#[cfg(feature = "tui")]
+use std::{
+    io::IsTerminal,
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "tui")]
+use crossterm::{
+    cursor::{Hide, MoveTo, Show},
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute, queue,
+    style::Print,
+    terminal::{
+        self, disable_raw_mode, enable_raw_mode, Clear, ClearType,
+        EnterAlternateScreen, LeaveAlternateScreen,
+    },
+};
+
+use super::{Animation, Frame, Palette};
+use crate::{grid::Grid, style};
+
+#[cfg(feature = "tui")]
+const HELP: &str = "[space] pause  [←/→] step  [+/-] speed  [q] skip to end";
+
+/// Raw mode on the alternate screen, left again when dropped, so that an
+/// error or a panic does not leave the terminal unusable.
+#[cfg(feature = "tui")]
+pub struct FullScreen;
+
+#[cfg(feature = "tui")]
+impl FullScreen {
+    pub fn enter() -> Result<Self, Box<dyn Error>> {
+        enable_raw_mode()?;
+        // From here on, dropping the guard undoes what was entered.
+        let screen = Self;
+        execute!(io::stdout(), EnterAlternateScreen, Hide)?;
+        Ok(screen)
+    }
+}
+
+#[cfg(feature = "tui")]
+impl Drop for FullScreen {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), Show, LeaveAlternateScreen);
+        let _ = disable_raw_mode();
+    }
+}
+
+/// Play `animation` in place on the terminal and then leave its final frame
+/// printed below the rest of the output. When stdout or stdin is not a
+/// terminal, or without the `tui` feature, only the final frame is printed.
+pub fn play(animation: &Animation, fps: f32) -> Result<(), Box<dyn Error>> {
+    let last = match animation.frames.last() {
+        Some(frame) => frame,
+        None => return Ok(()),
+    };
+    let mut stdout = io::stdout();
+    let size = play_full_screen(&mut stdout, animation, fps)?;
+
+    println!(
+        "{}",
+        style::THEME.heading.paint(format!("--- {} ---", animation.title))
+    );
+    draw(&mut stdout, animation.palette, last, size, "\n")?;
+    if !last.caption.is_empty() {
+        println!("{}", last.caption);
+    }
+    println!();
+    Ok(())
+}
+
+/// Play `animation` full screen when stdout and stdin are terminals, and
+/// give the size of the terminal to fit its final frame to.
+#[cfg(feature = "tui")]
+fn play_full_screen(
+    stdout: &mut io::Stdout,
+    animation: &Animation,
+    fps: f32,
+) -> Result<Option<(usize, usize)>, Box<dyn Error>> {
+    if stdout.is_terminal() && io::stdin().is_terminal() {
+        let screen = FullScreen::enter()?;
+        play_frames(stdout, animation, fps)?;
+        drop(screen);
+    }
+    let size = stdout
+        .is_terminal()
+        .then(|| terminal::size().map(|(c, r)| (c as usize, r as usize)))
+        .transpose()?;
+    Ok(size)
+}
+
+/// Without the `tui` feature the size of the terminal is unknown, so the
+/// final frame is printed whole.
+#[cfg(not(feature = "tui"))]
+fn play_full_screen(
+    _: &mut io::Stdout,
+    _: &Animation,
+    _: f32,
+) -> Result<Option<(usize, usize)>, Box<dyn Error>> {
+    Ok(None)
+}
+
+#[cfg(feature = "tui")]
+fn play_frames(
+    out: &mut impl Write,
+    animation: &Animation,
+    fps: f32,
+) -> Result<(), Box<dyn Error>> {
+    let n_frames = animation.frames.len();
+    let mut fps = fps.max(0.1);
+    let mut i = 0;
+    let mut paused = false;
+
+    loop {
+        let (cols, rows) = terminal::size()?;
+        let frame = &animation.frames[i];
+        queue!(out, MoveTo(0, 0), Clear(ClearType::All))?;
+        queue!(
+            out,
+            Print(format!(
+                "{}  frame {}/{}  {:.1} fps{}\r\n{}\r\n",
+                animation.title,
+                i + 1,
+                n_frames,
+                fps,
+                if paused { "  (paused)" } else { "" },
+                HELP
+            ))
+        )?;
+        let size = (cols as usize, (rows as usize).saturating_sub(3));
+        draw(out, animation.palette, frame, Some(size), "\r\n")?;
+        queue!(out, Print(&frame.caption))?;
+        out.flush()?;
+
+        let deadline = Instant::now() + Duration::from_secs_f32(1.0 / fps);
+        let key = next_key((!paused).then_some(deadline))?;
+
+        match key.map(|k| (k.code, k.modifiers)) {
+            None if i + 1 == n_frames => return Ok(()),
+            None => i += 1,
+            Some((KeyCode::Char('q') | KeyCode::Esc, _)) => return Ok(()),
+            Some((KeyCode::Char('c'), KeyModifiers::CONTROL)) => {
+                return Err("interrupted".into())
+            }
+            Some((KeyCode::Char(' '), _)) => paused = !paused,
+            Some((KeyCode::Right | KeyCode::Char('.'), _)) => {
+                paused = true;
+                i = (i + 1).min(n_frames - 1);
+            }
+            Some((KeyCode::Left | KeyCode::Char(','), _)) => {
+                paused = true;
+                i = i.saturating_sub(1);
+            }
+            Some((KeyCode::Char('+') | KeyCode::Char('='), _)) => fps *= 1.5,
+            Some((KeyCode::Char('-'), _)) => fps = (fps / 1.5).max(0.1),
+            Some(_) => (),
+        }
+    }
+}
+
+/// Wait for a key press until `deadline`, or forever if there is none.
+#[cfg(feature = "tui")]
+fn next_key(
+    deadline: Option<Instant>,
+) -> Result<Option<KeyEvent>, Box<dyn Error>> {
+    loop {
+        let timeout = match deadline {
+            Some(d) => d.saturating_duration_since(Instant::now()),
+            None => Duration::from_secs(3600),
+        };
+        if deadline.is_some() && timeout.is_zero() {
+            return Ok(None);
+        }
+        if event::poll(timeout)? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    return Ok(Some(key));
+                }
+            }
+        }
+    }
+}
+
+/// Draw a frame, shrunk to fit `size` by keeping the highest value of each
+/// block of cells.
+fn draw(
+    out: &mut impl Write,
+    palette: Palette,
+    frame: &Frame,
+    size: Option<(usize, usize)>,
+    newline: &str,
+) -> Result<(), Box<dyn Error>> {
+    let colored = style::enabled();
+    let cells = match size {
+        Some((cols, rows)) => shrink_to_fit(&frame.cells, cols, rows),
+        None => frame.cells.clone(),
+    };
+
+    let mut color = None;
+    for row in cells.rows() {
+        for &value in row {
+            let swatch = palette[value as usize % palette.len()];
+            if colored && color != Some(swatch.color) {
+                write!(out, "{}", style::rgb(swatch.color))?;
+                color = Some(swatch.color);
+            }
+            write!(out, "{}", swatch.glyph)?;
+        }
+        if colored {
+            write!(out, "{}", style::RESET)?;
+        }
+        write!(out, "{}", newline)?;
+        color = None;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+fn shrink_to_fit(cells: &Grid<u8>, cols: usize, rows: usize) -> Grid<u8> {
+    let scale = cells
+        .width()
+        .div_ceil(cols.max(1))
+        .max(cells.height().div_ceil(rows.max(1)))
+        .max(1);
+    if scale == 1 {
+        return cells.clone();
+    }
+
+    Grid::from_fn(
+        cells.width().div_ceil(scale),
+        cells.height().div_ceil(scale),
+        |x, y| {
+            (y * scale..((y + 1) * scale).min(cells.height()))
+                .flat_map(|cy| {
+                    (x * scale..((x + 1) * scale).min(cells.width()))
+                        .map(move |cx| (cx, cy))
+                })
+                .map(|pos| cells[pos])
+                .max()
+                .unwrap_or(0)
+        },
+    )
+}
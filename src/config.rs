@@ -0,0 +1,80 @@
+use std::{
+    collections::BTreeMap,
+    env,
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use serde::Deserialize;
+
+const CONFIG_FILE: &str = "aoc.toml";
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Settings from `aoc.toml`, looked up in the current directory and then in
+/// the crate root. Relative paths are relative to the file's directory.
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub inputs_dir: Option<PathBuf>,
+    /// Where `--bench` saves its results, `bench-history.toml` in the crate
+    /// root by default.
+    pub history: Option<PathBuf>,
+    /// Where to load plugin days from, `plugins` in the crate root by
+    /// default.
+    pub plugins_dir: Option<PathBuf>,
+    /// Where `all` keeps the answers it found, `answer-cache.toml` in the
+    /// crate root by default.
+    pub cache: Option<PathBuf>,
+    pub budget: Budget,
+}
+
+/// The `[budget]` table: how much time the `budget` command allows.
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Budget {
+    /// Milliseconds for all the days together.
+    pub total: Option<f64>,
+    /// Milliseconds set aside for some days, by name (`day12 = 300`). The
+    /// rest of the total is split evenly among the other days.
+    pub days: BTreeMap<String, f64>,
+}
+
+impl Config {
+    pub fn load() -> Result<Self, Box<dyn Error>> {
+        let candidates = [
+            env::current_dir()?.join(CONFIG_FILE),
+            Path::new(env!("CARGO_MANIFEST_DIR")).join(CONFIG_FILE),
+        ];
+        match candidates.iter().find(|p| p.is_file()) {
+            Some(path) => Self::load_from(path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    fn load_from(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let mut config: Self = toml::from_str(&fs::read_to_string(path)?)
+            .map_err(|e| format!("invalid {}: {}", path.display(), e))?;
+
+        if let Some(dir) = path.parent() {
+            config.inputs_dir = config.inputs_dir.map(|d| dir.join(d));
+            config.history = config.history.map(|h| dir.join(h));
+            config.plugins_dir = config.plugins_dir.map(|d| dir.join(d));
+            config.cache = config.cache.map(|c| dir.join(c));
+        }
+
+        Ok(config)
+    }
+}
+
+/// Install the configuration for the rest of the run. Only the first call
+/// has an effect.
+pub fn init(config: Config) {
+    let _ = CONFIG.set(config);
+}
+
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(|| Config::load().unwrap_or_default())
+}
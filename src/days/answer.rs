@@ -0,0 +1,144 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// A puzzle answer. `Grid` answers are rows of a picture where `#` marks a
+/// lit cell, e.g. letters drawn by folding day 13's paper.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "lowercase")]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+    Grid(Vec<String>),
+}
+
+impl Answer {
+    /// The form of the answer that the puzzle website accepts.
+    pub fn submission(&self) -> Result<String, Box<dyn Error>> {
+        match self {
+            Self::Integer(n) => Ok(n.to_string()),
+            Self::Text(s) => Ok(s.clone()),
+            Self::Grid(rows) => read_letters(rows),
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Integer(n) => n.fmt(f),
+            Self::Text(s) => f.write_str(s),
+            Self::Grid(rows) => f.write_str(&rows.join("\n")),
+        }
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty),+) => {
+        $(impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                Self::Integer(n as i128)
+            }
+        })+
+    };
+}
+
+impl_from_int!(i32, i64, isize, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Self::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Self::Text(s.to_string())
+    }
+}
+
+const LETTER_WIDTH: usize = 4;
+const LETTER_HEIGHT: usize = 6;
+
+const LETTERS: &[(char, [&str; LETTER_HEIGHT])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// The letters that can be drawn and read back.
+#[cfg(feature = "letters")]
+pub fn drawable_letters() -> impl Iterator<Item = char> {
+    LETTERS.iter().map(|(c, _)| *c)
+}
+
+/// Draw `text` in the rows of `#` and ` ` that [`read_letters`] reads.
+#[cfg(feature = "letters")]
+pub fn draw_letters(text: &str) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut rows = vec![String::new(); LETTER_HEIGHT];
+    for (i, c) in text.chars().enumerate() {
+        let (_, glyph) = LETTERS
+            .iter()
+            .find(|(letter, _)| *letter == c)
+            .ok_or_else(|| format!("cannot draw letter: {}", c))?;
+        for (row, glyph_row) in rows.iter_mut().zip(glyph) {
+            if i > 0 {
+                row.push(' ');
+            }
+            row.extend(
+                glyph_row.chars().map(|c| if c == '#' { '#' } else { ' ' }),
+            );
+        }
+    }
+    Ok(rows)
+}
+
+/// Read the capital letters drawn in `rows`, which are four cells wide and
+/// six tall with one blank column between them.
+fn read_letters(rows: &[String]) -> Result<String, Box<dyn Error>> {
+    if rows.len() != LETTER_HEIGHT {
+        return Err(format!(
+            "grid must be {} rows tall to read letters, not {}",
+            LETTER_HEIGHT,
+            rows.len()
+        )
+        .into());
+    }
+
+    let lit = |y: usize, x: usize| rows[y].as_bytes().get(x) == Some(&b'#');
+    let width = rows.iter().map(String::len).max().unwrap_or(0);
+
+    let mut letters = String::new();
+    for left in (0..width).step_by(LETTER_WIDTH + 1) {
+        let (letter, _) = LETTERS
+            .iter()
+            .find(|(_, glyph)| {
+                glyph.iter().enumerate().all(|(y, row)| {
+                    row.bytes()
+                        .enumerate()
+                        .all(|(x, c)| lit(y, left + x) == (c == b'#'))
+                })
+            })
+            .ok_or_else(|| format!("unrecognized letter at column {}", left))?;
+        letters.push(*letter);
+    }
+
+    Ok(letters)
+}
@@ -0,0 +1,120 @@
+use std::error::Error;
+
+use super::{CliBudget, Day};
+use crate::{config, style::THEME, util::repeat_char};
+
+/// The whole challenge: every day in under a second.
+const DEFAULT_TOTAL_MS: f64 = 1000.0;
+
+/// Run every day once and compare its time with its share of the budget.
+/// Fails when the days together take longer than the budget.
+pub fn run(
+    opts: &CliBudget,
+    days: &[(u32, Box<dyn Day>)],
+) -> Result<(), Box<dyn Error>> {
+    let budgets = allocate(opts, days)?;
+    let total_budget: f64 = budgets.iter().sum();
+
+    println!();
+    println!(
+        "{}",
+        THEME.heading.paint(format!(
+            "Day  {:>11}  {:>11}  {:>7}  {:>11}",
+            "Budget (ms)", "Time (ms)", "Share", "Total (ms)"
+        ))
+    );
+    println!("{}", repeat_char!('-', 50));
+
+    let mut total = 0.0;
+    let mut over = Vec::new();
+    for ((n, day), &budget) in days.iter().zip(&budgets) {
+        let (ts1, ts2) = day.run()?;
+        let time = (ts1.time + ts2.time) as f64 / 1.0e3;
+        total += time;
+
+        let row = format!(
+            "{:3}  {:>11.3}  {:>11.3}  {:>6.1}%  {:>11.3}",
+            n,
+            budget,
+            time,
+            100.0 * time / total_budget,
+            total
+        );
+        let failed = ts1.solution.is_err() || ts2.solution.is_err();
+        if failed {
+            println!("{}  {}", row, THEME.error.paint("error"));
+        } else if time > budget {
+            println!("{}  {}", row, THEME.error.paint("over"));
+        } else {
+            println!("{}", row);
+        }
+        if failed || time > budget {
+            over.push(*n);
+        }
+    }
+    println!("{}", repeat_char!('-', 50));
+    println!(
+        "     {:>11.3}  {:>11.3}  {:>6.1}%",
+        total_budget,
+        total,
+        100.0 * total / total_budget
+    );
+    println!();
+
+    if !over.is_empty() {
+        let days: Vec<_> = over.iter().map(|n| format!("day{}", n)).collect();
+        println!(
+            "{}",
+            THEME
+                .muted
+                .paint(format!("[over their own budget: {}]", days.join(", ")))
+        );
+    }
+    if total <= total_budget {
+        println!(
+            "{} {:.3} ms of {:.3} ms",
+            THEME.answer.paint("PASS"),
+            total,
+            total_budget
+        );
+        Ok(())
+    } else {
+        Err(format!("over budget: {:.3} ms of {:.3} ms", total, total_budget)
+            .into())
+    }
+}
+
+/// The budget of each day in milliseconds: the days named in the config
+/// get theirs, and the others split what is left evenly.
+fn allocate(
+    opts: &CliBudget,
+    days: &[(u32, Box<dyn Day>)],
+) -> Result<Vec<f64>, Box<dyn Error>> {
+    let config = &config::get().budget;
+    let total = opts.total.or(config.total).unwrap_or(DEFAULT_TOTAL_MS);
+
+    let mut fixed: Vec<Option<f64>> = vec![None; days.len()];
+    for (name, &ms) in &config.days {
+        let n: u32 = name
+            .strip_prefix("day")
+            .and_then(|n| n.parse().ok())
+            .ok_or_else(|| format!("budget for unknown day: {}", name))?;
+        // Days left out of the build are skipped, so that one config works
+        // with any set of days.
+        if let Some(i) = days.iter().position(|(d, _)| *d == n) {
+            fixed[i] = Some(ms);
+        }
+    }
+
+    let reserved: f64 = fixed.iter().flatten().sum();
+    if reserved > total {
+        return Err(format!(
+            "the day budgets add up to {} ms, more than the total of {} ms",
+            reserved, total
+        )
+        .into());
+    }
+    let n_rest = fixed.iter().filter(|ms| ms.is_none()).count().max(1);
+    let even = (total - reserved) / n_rest as f64;
+    Ok(fixed.iter().map(|ms| ms.unwrap_or(even)).collect())
+}
@@ -0,0 +1,160 @@
+use std::{
+    env,
+    error::Error,
+    fmt::Write,
+    fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::{params::value_of, Answer, Day, TimedSolution};
+use crate::{config, input};
+
+const CACHE_FILE: &str = "answer-cache.toml";
+
+/// An answer found before, for a day and part that read the same input
+/// with the same parameters in the same build.
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    day: u32,
+    part: u8,
+    /// SHA-256 of the input, after decryption.
+    input: String,
+    /// The parameters of the day as `name=value`, separated by spaces.
+    params: String,
+    answer: Answer,
+    /// Microseconds the part took when it was solved.
+    time_us: f32,
+}
+
+/// The answers of one build, loaded once for a run of `all` and saved once
+/// at its end. Entries from any other build are stale, so the file only
+/// holds the entries of one build.
+#[derive(Default, Serialize, Deserialize)]
+pub struct Cache {
+    build: String,
+    #[serde(default)]
+    answer: Vec<Entry>,
+    /// Whether answers were added since the file was loaded.
+    #[serde(skip)]
+    changed: bool,
+}
+
+/// The cache file from the config, or `answer-cache.toml` in the crate
+/// root.
+fn path() -> PathBuf {
+    config::get().cache.clone().unwrap_or_else(|| {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join(CACHE_FILE)
+    })
+}
+
+/// The SHA-256 of the running executable, which changes with the code.
+fn build() -> Result<String, Box<dyn Error>> {
+    let exe = fs::read(env::current_exe()?)?;
+    Ok(hex(&Sha256::digest(exe)))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut s, b| {
+        let _ = write!(s, "{:02x}", b);
+        s
+    })
+}
+
+/// Delete the cache file.
+pub fn clear() -> Result<(), Box<dyn Error>> {
+    let path = path();
+    match fs::remove_file(&path) {
+        Ok(()) => println!("removed {}", path.display()),
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            println!("no cache at {}", path.display())
+        }
+        Err(e) => return Err(e.into()),
+    }
+    Ok(())
+}
+
+impl Cache {
+    /// The cached answers of this build, none if the file is missing or
+    /// from another build.
+    pub fn load() -> Result<Self, Box<dyn Error>> {
+        let build = build()?;
+        let path = path();
+        let cache: Self = match fs::read_to_string(&path) {
+            Ok(text) => toml::from_str(&text)
+                .map_err(|e| format!("invalid {}: {}", path.display(), e))?,
+            Err(e) if e.kind() == ErrorKind::NotFound => Self::default(),
+            Err(e) => return Err(e.into()),
+        };
+        if cache.build != build {
+            return Ok(Self { build, ..Self::default() });
+        }
+        Ok(cache)
+    }
+
+    /// Write the file if answers were added to it.
+    pub fn save(&self) -> Result<(), Box<dyn Error>> {
+        if !self.changed {
+            return Ok(());
+        }
+        let path = path();
+        fs::write(&path, toml::to_string(self)?)
+            .map_err(|e| format!("cannot save to {}: {}", path.display(), e))?;
+        Ok(())
+    }
+
+    /// Both parts of day `n` reading `infile`, taken from the cache where
+    /// it has them. The parts solved successfully are added to it.
+    pub fn run(
+        &mut self,
+        n: u32,
+        day: &dyn Day,
+        infile: &Path,
+    ) -> Result<(TimedSolution, TimedSolution), Box<dyn Error>> {
+        // Without an input the parts fail, and failures are not cached.
+        let input = match input::load(infile) {
+            Ok(file) => hex(&Sha256::digest(&*file)),
+            Err(_) => return Ok((day.run_part1()?, day.run_part2()?)),
+        };
+        let params = day
+            .params()
+            .iter()
+            .map(|p| format!("{}={}", p.name(), value_of(n, *p)))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let mut solve = |part: u8| -> Result<TimedSolution, Box<dyn Error>> {
+            let found = self.answer.iter().find(|e| {
+                (e.day, e.part, &e.input, &e.params)
+                    == (n, part, &input, &params)
+            });
+            if let Some(entry) = found {
+                return Ok(TimedSolution::cached(
+                    entry.answer.clone(),
+                    entry.time_us,
+                ));
+            }
+
+            let timed = match part {
+                1 => day.run_part1()?,
+                _ => day.run_part2()?,
+            };
+            if let Ok(answer) = &timed.solution {
+                self.answer.push(Entry {
+                    day: n,
+                    part,
+                    input: input.clone(),
+                    params: params.clone(),
+                    answer: answer.clone(),
+                    time_us: timed.time,
+                });
+                self.changed = true;
+            }
+            Ok(timed)
+        };
+        Ok((solve(1)?, solve(2)?))
+    }
+}
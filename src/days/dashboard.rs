@@ -0,0 +1,333 @@
+use std::{
+    error::Error,
+    io::{self, IsTerminal, Write},
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+};
+
+use crossterm::{
+    cursor::{Hide, MoveTo, Show},
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    execute, queue,
+    style::Print,
+    terminal::{
+        self, disable_raw_mode, enable_raw_mode, Clear, ClearType,
+        EnterAlternateScreen, LeaveAlternateScreen,
+    },
+};
+
+use super::{Answer, Day, TimedSolution};
+use crate::{
+    animation::{self, FullScreen},
+    style::THEME,
+};
+
+const HELP: &str = "[↑/↓] select  [1/2] run part  [r] run both  \
+                    [i] switch input  [v] visualize  [q] quit";
+/// Answers longer than this are cut short in the table.
+const ANSWER_WIDTH: usize = 16;
+
+/// The outcome of the last run of a part.
+struct Outcome {
+    answer: Result<Answer, String>,
+    /// Microseconds.
+    time: f32,
+}
+
+impl Outcome {
+    fn new(solution: Result<TimedSolution, Box<dyn Error>>) -> Self {
+        match solution {
+            Ok(ts) => Self {
+                answer: ts.solution.map_err(|e| e.to_string()),
+                time: ts.time,
+            },
+            Err(e) => Self { answer: Err(e.to_string()), time: 0.0 },
+        }
+    }
+
+    /// The answer on one line, as it fits in the table.
+    fn summary(&self) -> String {
+        let text = match &self.answer {
+            Ok(Answer::Grid(_)) => "[picture]".to_string(),
+            Ok(answer) => answer.to_string(),
+            Err(_) => return "error".to_string(),
+        };
+        if text.chars().count() > ANSWER_WIDTH {
+            let cut: String = text.chars().take(ANSWER_WIDTH - 1).collect();
+            cut + "…"
+        } else {
+            text
+        }
+    }
+}
+
+struct Entry {
+    day: u32,
+    /// The real input and then the examples.
+    inputs: Vec<PathBuf>,
+    input: usize,
+    parts: [Option<Outcome>; 2],
+}
+
+impl Entry {
+    fn input_name(&self) -> String {
+        if self.input == 0 {
+            return "real".to_string();
+        }
+        let path = &self.inputs[self.input];
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        name.trim_end_matches("-input.txt").to_string()
+    }
+}
+
+struct Dashboard<F> {
+    entries: Vec<Entry>,
+    selected: usize,
+    /// Shown at the bottom after an action.
+    status: String,
+    with_input: F,
+    fps: f32,
+}
+
+/// Run the full-screen dashboard over `days`, which pairs each day with its
+/// inputs. `with_input` makes a day that reads the given input.
+pub fn run<F>(
+    days: Vec<(u32, Vec<PathBuf>)>,
+    with_input: F,
+    fps: f32,
+) -> Result<(), Box<dyn Error>>
+where
+    F: Fn(u32, &Path) -> Box<dyn Day>,
+{
+    if !io::stdout().is_terminal() || !io::stdin().is_terminal() {
+        return Err("the dashboard needs a terminal".into());
+    }
+    if days.is_empty() {
+        return Err("there are no days to show".into());
+    }
+
+    let mut dashboard = Dashboard {
+        entries: days
+            .into_iter()
+            .map(|(day, inputs)| Entry {
+                day,
+                inputs,
+                input: 0,
+                parts: [None, None],
+            })
+            .collect(),
+        selected: 0,
+        status: String::new(),
+        with_input,
+        fps,
+    };
+
+    let _screen = FullScreen::enter()?;
+    dashboard.event_loop(&mut io::stdout())
+}
+
+impl<F> Dashboard<F>
+where
+    F: Fn(u32, &Path) -> Box<dyn Day>,
+{
+    fn event_loop(
+        &mut self,
+        out: &mut impl Write,
+    ) -> Result<(), Box<dyn Error>> {
+        loop {
+            self.draw(out)?;
+            let key = match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => key,
+                _ => continue,
+            };
+            let entry = &mut self.entries[self.selected];
+            match (key.code, key.modifiers) {
+                (KeyCode::Char('q') | KeyCode::Esc, _) => return Ok(()),
+                (KeyCode::Char('c'), KeyModifiers::CONTROL) => return Ok(()),
+                (KeyCode::Up | KeyCode::Char('k'), _) => {
+                    self.selected = self.selected.saturating_sub(1);
+                }
+                (KeyCode::Down | KeyCode::Char('j'), _) => {
+                    self.selected =
+                        (self.selected + 1).min(self.entries.len() - 1);
+                }
+                (KeyCode::Char('i'), _) => {
+                    entry.input = (entry.input + 1) % entry.inputs.len();
+                    entry.parts = [None, None];
+                    self.status = format!(
+                        "day {} now reads {}",
+                        entry.day,
+                        entry.inputs[entry.input].display()
+                    );
+                }
+                (KeyCode::Char('1'), _) => self.run_parts(&[0])?,
+                (KeyCode::Char('2'), _) => self.run_parts(&[1])?,
+                (KeyCode::Char('r') | KeyCode::Enter, _) => {
+                    self.run_parts(&[0, 1])?
+                }
+                (KeyCode::Char('v'), _) => self.visualize(out)?,
+                _ => (),
+            }
+        }
+    }
+
+    fn selected_day(&self) -> Box<dyn Day> {
+        let entry = &self.entries[self.selected];
+        (self.with_input)(entry.day, &entry.inputs[entry.input])
+    }
+
+    fn run_parts(&mut self, parts: &[usize]) -> Result<(), Box<dyn Error>> {
+        let day = self.selected_day();
+        let entry = &mut self.entries[self.selected];
+        for &part in parts {
+            let solution =
+                panic::catch_unwind(AssertUnwindSafe(|| match part {
+                    0 => day.run_part1(),
+                    _ => day.run_part2(),
+                }))
+                .unwrap_or_else(|_| Err("the solver panicked".into()));
+            entry.parts[part] = Some(Outcome::new(solution));
+        }
+        self.status = format!(
+            "ran day {} on the {} input",
+            entry.day,
+            entry.input_name()
+        );
+        Ok(())
+    }
+
+    /// Solve the selected day while recording, then play what was recorded
+    /// on the normal screen.
+    fn visualize(
+        &mut self,
+        out: &mut impl Write,
+    ) -> Result<(), Box<dyn Error>> {
+        animation::start_recording();
+        self.run_parts(&[0, 1])?;
+        animation::stop_recording();
+        let animations = animation::take_recorded();
+        if animations.is_empty() {
+            self.status = format!(
+                "day {} has no visualization",
+                self.entries[self.selected].day
+            );
+            return Ok(());
+        }
+
+        execute!(out, Show, LeaveAlternateScreen)?;
+        disable_raw_mode()?;
+        let result = animation::play(&animations, self.fps);
+        enable_raw_mode()?;
+        execute!(out, EnterAlternateScreen, Hide)?;
+        result
+    }
+
+    fn draw(&self, out: &mut impl Write) -> Result<(), Box<dyn Error>> {
+        let (_, rows) = terminal::size()?;
+        let mut lines = vec![
+            THEME.banner.paint("Advent of Code 2021").to_string(),
+            THEME.muted.paint(HELP).to_string(),
+            String::new(),
+            THEME
+                .heading
+                .paint(format!(
+                    "  {:>3}  {:<8}  {:<16}  {:>10}  {:<16}  {:>10}",
+                    "Day", "Input", "Part 1", "ms", "Part 2", "ms"
+                ))
+                .to_string(),
+        ];
+
+        for (i, entry) in self.entries.iter().enumerate() {
+            let label = format!("{:>3}  {:<8}", entry.day, entry.input_name());
+            let mut line = if i == self.selected {
+                THEME.highlight.paint(format!("> {}", label)).to_string()
+            } else {
+                format!("  {}", label)
+            };
+            for part in &entry.parts {
+                let (answer, time) = match part {
+                    Some(o) => (o.summary(), format!("{:.3}", o.time / 1.0e3)),
+                    None => ("·".to_string(), String::new()),
+                };
+                let answer = format!("{:<16}", answer);
+                let answer = match part.as_ref().map(|o| &o.answer) {
+                    Some(Ok(_)) => THEME.answer.paint(answer).to_string(),
+                    Some(Err(_)) => THEME.error.paint(answer).to_string(),
+                    None => THEME.muted.paint(answer).to_string(),
+                };
+                line += &format!("  {}  {:>10}", answer, time);
+            }
+            lines.push(line);
+        }
+
+        lines.push(String::new());
+        lines.extend(self.details());
+        let height = rows.saturating_sub(1) as usize;
+        lines.truncate(height);
+        while lines.len() < height {
+            lines.push(String::new());
+        }
+        lines.push(THEME.muted.paint(&self.status).to_string());
+
+        queue!(out, MoveTo(0, 0), Clear(ClearType::All))?;
+        queue!(out, Print(lines.join("\r\n")))?;
+        out.flush()?;
+        Ok(())
+    }
+
+    /// The full answers and errors of the selected day.
+    fn details(&self) -> Vec<String> {
+        let entry = &self.entries[self.selected];
+        let mut lines = vec![THEME
+            .heading
+            .paint(format!(
+                "Day {} on {}",
+                entry.day,
+                entry.inputs[entry.input].display()
+            ))
+            .to_string()];
+
+        for (i, part) in entry.parts.iter().enumerate() {
+            let outcome = match part {
+                Some(outcome) => outcome,
+                None => {
+                    lines.push(format!("Part {}: not run", i + 1));
+                    continue;
+                }
+            };
+            let time =
+                THEME.muted.paint(format!("[{:.3} ms]", outcome.time / 1.0e3));
+            match &outcome.answer {
+                Ok(answer @ Answer::Grid(rows)) => {
+                    lines.push(format!("Part {}: {}", i + 1, time));
+                    lines.extend(
+                        rows.iter()
+                            .map(|row| THEME.answer.paint(row).to_string()),
+                    );
+                    if let Ok(letters) = answer.submission() {
+                        lines.push(
+                            THEME
+                                .muted
+                                .paint(format!("[reads as {}]", letters))
+                                .to_string(),
+                        );
+                    }
+                }
+                Ok(answer) => lines.push(format!(
+                    "Part {}: {} {}",
+                    i + 1,
+                    THEME.answer.paint(answer),
+                    time
+                )),
+                Err(e) => {
+                    lines.push(format!("Part {}: {}", i + 1, time));
+                    lines.extend(
+                        e.lines()
+                            .map(|line| THEME.error.paint(line).to_string()),
+                    );
+                }
+            }
+        }
+        lines
+    }
+}
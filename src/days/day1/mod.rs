@@ -1,29 +1,48 @@
-use std::{
-    error::Error,
-    fs::File,
-    io::{BufRead, BufReader},
-    path::PathBuf,
-};
+use std::{error::Error, fmt::Write, path::PathBuf};
 
+use rand::Rng;
 use structopt::StructOpt;
 
-use super::{todays_input, Day, PartResult, ANSWER};
+use super::{
+    generate::InputRng,
+    params::{param, Param, ParamSpec},
+    todays_input, Day, GenerateResult, PartResult, ANSWER,
+};
+use crate::{input, parse};
+
+todays_input!(Day1);
 
-todays_input!(INFILE_PATH);
+static PART1_WINDOW: Param<usize> =
+    param!("part1-window", 1, "number of depths summed per window in part 1");
+static PART2_WINDOW: Param<usize> =
+    param!("part2-window", 3, "number of depths summed per window in part 2");
 
 #[derive(StructOpt)]
 pub struct Day1 {
-    #[structopt(default_value = &INFILE_PATH)]
-    infile: PathBuf,
+    infile: Option<PathBuf>,
 }
 
 impl Day for Day1 {
+    fn params(&self) -> Vec<&'static dyn ParamSpec> {
+        vec![&PART1_WINDOW, &PART2_WINDOW]
+    }
+
+    fn generate(&self, rng: &mut InputRng, size: usize) -> GenerateResult {
+        let mut out = String::new();
+        let mut depth: i64 = rng.random_range(100..200);
+        for _ in 0..2000 * size {
+            writeln!(out, "{}", depth)?;
+            depth = (depth + rng.random_range(-20..=30)).max(0);
+        }
+        Ok(out)
+    }
+
     fn part1(&self) -> PartResult {
-        ANSWER!(self.calc_depth_increase(1)?)
+        ANSWER!(self.calc_depth_increase(PART1_WINDOW.get()?)?)
     }
 
     fn part2(&self) -> PartResult {
-        ANSWER!(self.calc_depth_increase(3)?)
+        ANSWER!(self.calc_depth_increase(PART2_WINDOW.get()?)?)
     }
 }
 
@@ -32,7 +51,13 @@ impl Day1 {
         &self,
         window_size: usize,
     ) -> Result<usize, Box<dyn Error>> {
-        let file = BufReader::new(File::open(&self.infile)?);
+        if window_size == 0 {
+            return Err("window size must be at least 1".into());
+        }
+
+        let file = input::load(self.infile())?;
+
+        let text = file.text()?;
 
         let mut window = vec![0_isize; window_size];
         let mut window_len = 0;
@@ -40,13 +65,8 @@ impl Day1 {
         let mut prev_sum = 0;
         let mut answer = 0_usize;
 
-        for line_res in file.lines() {
-            let line = line_res
-                .map_err(|e| format!("invalid line: {}", e).to_string())?;
-
-            let depth = line
-                .parse()
-                .map_err(|_| format!("invalid depth: {}", line).to_string())?;
+        for line in parse::lines(text) {
+            let depth = line.parse()?;
 
             if window_len < window_size {
                 window[window_len] = depth;
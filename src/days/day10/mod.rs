@@ -1,34 +1,83 @@
-use std::{
-    error::Error,
-    fs::File,
-    io::{BufRead, BufReader},
-    path::PathBuf,
-};
+use std::{error::Error, path::PathBuf};
 
+use rand::Rng;
 use structopt::StructOpt;
+use tracing::instrument;
 
-use super::{todays_input, Day, PartResult, ANSWER};
+use super::{
+    explain::{self, Fact},
+    generate::InputRng,
+    todays_input, Day, GenerateResult, PartResult, ANSWER,
+};
+use crate::{input, parse};
 
 mod model;
 use self::model::NavChunk;
 
-todays_input!(INFILE_PATH);
+todays_input!(Day10);
 
 #[derive(StructOpt)]
 pub struct Day10 {
-    #[structopt(default_value = &INFILE_PATH)]
-    infile: PathBuf,
+    infile: Option<PathBuf>,
 }
 
 impl Day for Day10 {
+    fn generate(&self, rng: &mut InputRng, size: usize) -> GenerateResult {
+        const PAIRS: [(char, char); 4] =
+            [('(', ')'), ('[', ']'), ('{', '}'), ('<', '>')];
+
+        let n_lines = 100 * size;
+        let mut out = String::new();
+        let mut n_incomplete = 0;
+        for i in 0..n_lines {
+            // Part 2 takes the middle score, so there must be an odd number
+            // of incomplete lines.
+            let incomplete = if i + 1 == n_lines {
+                n_incomplete % 2 == 0
+            } else {
+                rng.random_bool(0.5)
+            };
+
+            let mut closers = Vec::new();
+            for _ in 0..rng.random_range(60..110) {
+                if closers.is_empty() || rng.random_bool(0.55) {
+                    let (open, close) = PAIRS[rng.random_range(0..4)];
+                    out.push(open);
+                    closers.push(close);
+                } else {
+                    out.extend(closers.pop());
+                }
+            }
+            if closers.is_empty() {
+                let (open, close) = PAIRS[rng.random_range(0..4)];
+                out.push(open);
+                closers.push(close);
+            }
+
+            if incomplete {
+                n_incomplete += 1;
+            } else {
+                let expected = closers[closers.len() - 1];
+                let wrong: Vec<_> = PAIRS
+                    .iter()
+                    .map(|p| p.1)
+                    .filter(|&c| c != expected)
+                    .collect();
+                out.push(wrong[rng.random_range(0..wrong.len())]);
+            }
+            out.push('\n');
+        }
+        Ok(out)
+    }
+
     fn part1(&self) -> PartResult {
         let chunks = self.parse_nav_chunks()?;
         let mut stack = Vec::new();
 
         let mut score = 0_usize;
-        for line in &chunks {
+        for (i, line) in chunks.iter().enumerate() {
             stack.clear();
-            for chunk_end in line {
+            for (col, chunk_end) in line.iter().enumerate() {
                 match chunk_end {
                     NavChunk::RoundOpen => stack.push(NavChunk::RoundClose),
                     NavChunk::SquareOpen => stack.push(NavChunk::SquareClose),
@@ -36,7 +85,17 @@ impl Day for Day10 {
                     NavChunk::AngledOpen => stack.push(NavChunk::AngledClose),
                     close => match stack.pop() {
                         Some(expected_close) if *close == expected_close => (),
-                        _ => {
+                        expected_close => {
+                            explain::fact(|| {
+                                let expected = match &expected_close {
+                                    Some(c) => c.to_string(),
+                                    None => "nothing".to_string(),
+                                };
+                                Fact::new(format!("line {}", i + 1))
+                                    .with("column", col + 1)
+                                    .with("corrupt", close)
+                                    .with("expected", expected)
+                            });
                             score += close.syntax_error_score()?;
                             break;
                         }
@@ -53,7 +112,7 @@ impl Day for Day10 {
         let mut stack = Vec::new();
         let mut scores = Vec::new();
 
-        'line: for line in &chunks {
+        'line: for (i, line) in chunks.iter().enumerate() {
             stack.clear();
             let mut score = 0_usize;
 
@@ -70,6 +129,12 @@ impl Day for Day10 {
                 }
             }
 
+            explain::fact(|| {
+                let completion: String =
+                    stack.iter().rev().map(NavChunk::to_string).collect();
+                Fact::new(format!("line {}", i + 1))
+                    .with("completion", completion)
+            });
             while let Some(close) = stack.pop() {
                 score = score * 5 + close.completion_score()?;
             }
@@ -83,21 +148,13 @@ impl Day for Day10 {
 }
 
 impl Day10 {
+    #[instrument(name = "parse", level = "debug", skip_all)]
     fn parse_nav_chunks(&self) -> Result<Vec<Vec<NavChunk>>, Box<dyn Error>> {
-        let file = BufReader::new(File::open(&self.infile)?);
-
-        let mut chunks = Vec::new();
-
-        for line_res in file.lines() {
-            let line = line_res
-                .map_err(|e| format!("invalid line: {}", e).to_string())?;
-            chunks.push(
-                line.chars()
-                    .map(TryInto::try_into)
-                    .collect::<Result<Vec<_>, _>>()?,
-            );
-        }
-
+        let file = input::load(self.infile())?;
+        let text = file.text()?;
+        let chunks = parse::lines(text)
+            .map(|line| line.chars(NavChunk::try_from))
+            .collect::<Result<_, _>>()?;
         Ok(chunks)
     }
 }
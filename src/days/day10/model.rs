@@ -1,4 +1,7 @@
-use std::error::Error;
+use std::{
+    error::Error,
+    fmt::{self, Display},
+};
 
 #[derive(Debug, PartialEq)]
 pub enum NavChunk {
@@ -54,3 +57,20 @@ impl NavChunk {
         }
     }
 }
+
+impl Display for NavChunk {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use NavChunk::*;
+        let c = match self {
+            RoundOpen => '(',
+            RoundClose => ')',
+            SquareOpen => '[',
+            SquareClose => ']',
+            CurlyOpen => '{',
+            CurlyClose => '}',
+            AngledOpen => '<',
+            AngledClose => '>',
+        };
+        write!(f, "{}", c)
+    }
+}
@@ -1,33 +1,68 @@
 use std::path::PathBuf;
 
+use rand::Rng;
 use structopt::StructOpt;
 
-use super::{todays_input, Day, PartResult, ANSWER};
+use super::{
+    generate::InputRng,
+    params::{param, Param, ParamSpec},
+    snapshot::Session,
+    todays_input, Day, GenerateResult, PartResult, ANSWER,
+};
+use crate::animation::Recorder;
 
 mod model;
-use self::model::Octopuses;
+use self::model::{Octopuses, ENERGY_PALETTE};
 
-mod util;
+todays_input!(Day11);
 
-todays_input!(INFILE_PATH);
+static STEPS: Param<usize> =
+    param!("steps", 100, "number of steps simulated in part 1");
 
 #[derive(StructOpt)]
 pub struct Day11 {
-    #[structopt(default_value = &INFILE_PATH)]
-    infile: PathBuf,
+    infile: Option<PathBuf>,
 }
 
 impl Day for Day11 {
+    fn params(&self) -> Vec<&'static dyn ParamSpec> {
+        vec![&STEPS]
+    }
+
+    fn generate(&self, rng: &mut InputRng, size: usize) -> GenerateResult {
+        let side = 10 * size;
+        let mut out = String::with_capacity(side * (side + 1));
+        for _ in 0..side {
+            for _ in 0..side {
+                out.push(char::from(b'0' + rng.random_range(0..10)));
+            }
+            out.push('\n');
+        }
+        Ok(out)
+    }
+
     fn part1(&self) -> PartResult {
-        let octopuses = Octopuses::from(&self.infile)?;
-        ANSWER!(octopuses.take(100).sum::<usize>())
+        let mut octopuses = Octopuses::from(self.infile())?;
+        let session = Session::start(module_path!(), 1, &mut octopuses)?;
+        session.run(&mut octopuses, STEPS.get()?)?;
+        ANSWER!(octopuses.flashes())
     }
 
     fn part2(&self) -> PartResult {
-        let octopuses = Octopuses::from(&self.infile)?;
+        let mut octopuses = Octopuses::from(self.infile())?;
         let n_octopuses = octopuses.len();
-        let synchronized_step =
-            octopuses.take_while(|flashes| *flashes < n_octopuses).count();
-        ANSWER!(synchronized_step + 1)
+        let session = Session::start(module_path!(), 2, &mut octopuses)?;
+
+        let mut recorder =
+            Recorder::new("Day 11: Dumbo Octopus", ENERGY_PALETTE);
+        let mut step = session.start;
+        recorder.frame(|| octopuses.frame(format!("step {}", step)));
+        ANSWER!(session.run_until(&mut octopuses, |octopuses, flashes| {
+            step += 1;
+            recorder.frame(|| {
+                octopuses.frame(format!("step {}: {} flashes", step, flashes))
+            });
+            *flashes == n_octopuses
+        })?)
     }
 }
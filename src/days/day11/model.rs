@@ -2,82 +2,138 @@ use std::{
     collections::VecDeque,
     error::Error,
     fmt::{self, Debug},
-    fs::File,
-    io::{BufRead, BufReader},
     path::Path,
 };
 
-use super::util::ToNeighborhood;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use crate::{
+    animation::{Frame, Palette, Swatch},
+    days::snapshot::Snapshot,
+    grid::Grid,
+    input,
+    simulation::Simulation,
+    style::{Style, THEME},
+};
+
+/// Octopuses that just flashed are bright, the rest darken with their energy.
+pub const ENERGY_PALETTE: Palette = &[
+    Swatch::new('*', (255, 255, 160)),
+    Swatch::new('1', (20, 30, 70)),
+    Swatch::new('2', (30, 45, 95)),
+    Swatch::new('3', (40, 60, 120)),
+    Swatch::new('4', (50, 80, 145)),
+    Swatch::new('5', (60, 100, 170)),
+    Swatch::new('6', (70, 120, 190)),
+    Swatch::new('7', (85, 140, 210)),
+    Swatch::new('8', (100, 165, 225)),
+    Swatch::new('9', (120, 190, 240)),
+];
 
 pub struct Octopuses {
-    grid: Vec<Vec<u32>>,
+    grid: Grid<u32>,
     simulation_queue: VecDeque<(usize, usize)>,
+    /// Flashes over all the steps taken.
+    flashes: usize,
 }
 
 impl Octopuses {
+    #[instrument(name = "parse", level = "debug", skip_all)]
     pub fn from(path: impl AsRef<Path>) -> Result<Self, Box<dyn Error>> {
-        let file = BufReader::new(File::open(path.as_ref())?);
-
-        let mut grid = Vec::new();
+        let grid = Grid::parse_digits(input::load(path)?.text()?)?;
+        Ok(Self { grid, simulation_queue: VecDeque::new(), flashes: 0 })
+    }
 
-        for line_res in file.lines() {
-            let line = line_res
-                .map_err(|e| format!("invalid line: {}", e).to_string())?;
-            grid.push(
-                line.chars().map(|n| n as u32 - '0' as u32).collect::<Vec<_>>(),
-            );
-        }
+    pub fn len(&self) -> usize {
+        self.grid.len()
+    }
 
-        Ok(Self { grid, simulation_queue: VecDeque::new() })
+    pub fn flashes(&self) -> usize {
+        self.flashes
     }
 
-    pub fn len(&self) -> usize {
-        self.grid.iter().map(Vec::len).sum()
+    pub fn frame(&self, caption: String) -> Frame {
+        Frame::new(self.grid.map(|&o| o as u8), caption)
     }
+}
 
-    pub fn simulate_step(&mut self) -> usize {
-        self.simulation_queue
-            .extend((0..10).map(|y| (0..10).map(move |x| (x, y))).flatten());
+impl Simulation for Octopuses {
+    type Output = usize;
+    /// The flashes so far are part of the state, as part 1 counts them.
+    type State = (Grid<u32>, usize);
 
-        while let Some((x, y)) = self.simulation_queue.pop_front() {
-            let o = self.grid[y][x] + 1;
+    fn step(&mut self) -> usize {
+        self.simulation_queue.extend(self.grid.positions());
+
+        while let Some(pos) = self.simulation_queue.pop_front() {
+            let o = self.grid[pos] + 1;
             if o == 10 {
-                self.simulation_queue.extend((x, y).to_neighborhood());
+                self.simulation_queue.extend(self.grid.neighbors8(pos));
             }
-            self.grid[y][x] = o;
+            self.grid[pos] = o;
         }
 
         let mut flashes: usize = 0;
-        for o in self.grid.iter_mut().flatten() {
+        for o in self.grid.iter_mut() {
             if *o > 9 {
                 flashes += 1;
                 *o = 0;
             }
         }
 
+        self.flashes += flashes;
         flashes
     }
+
+    fn state(&self) -> Self::State {
+        (self.grid.clone(), self.flashes)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct OctopusesState {
+    energy: Vec<String>,
+    flashes: usize,
 }
 
-impl Iterator for Octopuses {
-    type Item = usize;
+impl Snapshot for Octopuses {
+    type State = OctopusesState;
 
-    fn next(&mut self) -> Option<Self::Item> {
-        Some(self.simulate_step())
+    fn save(&self) -> Self::State {
+        OctopusesState {
+            energy: self.grid.to_string().lines().map(String::from).collect(),
+            flashes: self.flashes,
+        }
+    }
+
+    fn restore(&mut self, state: Self::State) -> Result<(), Box<dyn Error>> {
+        let grid = Grid::parse_digits(&state.energy.join("\n"))?;
+        if (grid.width(), grid.height())
+            != (self.grid.width(), self.grid.height())
+        {
+            return Err(format!(
+                "the snapshot has {}x{} octopuses, the input {}x{}",
+                grid.width(),
+                grid.height(),
+                self.grid.width(),
+                self.grid.height()
+            )
+            .into());
+        }
+        self.grid = grid;
+        self.flashes = state.flashes;
+        Ok(())
     }
 }
 
 impl Debug for Octopuses {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        for row in &self.grid {
+        for row in self.grid.rows() {
             for o in row {
-                if *o == 0 {
-                    f.write_str("\x1b[92m")?;
-                }
-                f.write_fmt(format_args!("{:2} ", o))?;
-                if *o == 0 {
-                    f.write_str("\x1b[0m")?;
-                }
+                let style =
+                    if *o == 0 { THEME.highlight } else { Style::plain() };
+                write!(f, "{} ", style.paint(format_args!("{:2}", o)))?;
             }
             f.write_str("\n")?;
         }
@@ -1,32 +1,84 @@
-use std::{
-    error::Error,
-    fs::File,
-    io::{BufRead, BufReader},
-    path::PathBuf,
-};
+use std::{collections::HashSet, error::Error, fmt::Write, path::PathBuf};
 
+use rand::{seq::SliceRandom, Rng};
 use structopt::StructOpt;
+use tracing::instrument;
 
-use super::{todays_input, Day, PartResult, ANSWER};
+use super::{
+    generate::InputRng, todays_input, Day, GenerateResult, PartResult, ANSWER,
+};
+use crate::{
+    input,
+    parse::{self, ParseError},
+};
 
 mod model;
 use self::model::{CaveGraph, Node};
 
-todays_input!(INFILE_PATH);
+todays_input!(Day12);
 
 #[derive(StructOpt)]
 pub struct Day12 {
-    #[structopt(default_value = &INFILE_PATH)]
-    infile: PathBuf,
+    infile: Option<PathBuf>,
 }
 
 impl Day for Day12 {
+    fn generate(&self, rng: &mut InputRng, size: usize) -> GenerateResult {
+        let mut names = HashSet::new();
+        let mut name = |upper: bool| loop {
+            let base = if upper { b'A' } else { b'a' };
+            let name: String = (0..2)
+                .map(|_| char::from(base + rng.random_range(0..26)))
+                .collect();
+            if names.insert(name.clone()) {
+                return name;
+            }
+        };
+        let smalls: Vec<String> = (0..4 + size).map(|_| name(false)).collect();
+        let bigs: Vec<String> = (0..1 + size / 2).map(|_| name(true)).collect();
+
+        // A chain from start to end through every small cave, sometimes
+        // passing a big cave, keeps the graph connected. Big caves are never
+        // linked to each other, or there would be endless paths.
+        let mut edges = vec![("start".to_string(), smalls[0].clone())];
+        for pair in smalls.windows(2) {
+            if rng.random_bool(0.5) {
+                edges.push((pair[0].clone(), pair[1].clone()));
+            } else {
+                let big = &bigs[rng.random_range(0..bigs.len())];
+                edges.push((pair[0].clone(), big.clone()));
+                edges.push((big.clone(), pair[1].clone()));
+            }
+        }
+        edges.push((smalls[smalls.len() - 1].clone(), "end".to_string()));
+
+        for _ in 0..2 * size {
+            let small = &smalls[rng.random_range(0..smalls.len())];
+            let other = if rng.random_bool(0.5) {
+                &bigs[rng.random_range(0..bigs.len())]
+            } else {
+                &smalls[rng.random_range(0..smalls.len())]
+            };
+            if small != other {
+                edges.push((small.clone(), other.clone()));
+            }
+        }
+        edges.push(("start".to_string(), bigs[0].clone()));
+        edges.push((bigs[bigs.len() - 1].clone(), "end".to_string()));
+
+        edges.shuffle(rng);
+        let mut out = String::new();
+        for (a, b) in edges {
+            writeln!(out, "{}-{}", a, b)?;
+        }
+        Ok(out)
+    }
+
     fn part1(&self) -> PartResult {
         let cave = self.parse_cave_graph()?;
-        let answer = cave.find_all_paths_with(|visits, _, n| match visits[n] {
-            Some(v) if v >= 1 => true,
-            _ => false,
-        });
+        let answer = cave.find_all_paths_with(
+            |visits, _, n| matches!(visits[n], Some(v) if v >= 1),
+        );
         ANSWER!(answer)
     }
 
@@ -34,39 +86,26 @@ impl Day for Day12 {
         let cave = self.parse_cave_graph()?;
         let answer =
             cave.find_all_paths_with(|visits, small_double_visit, n| {
-                match visits[n] {
-                    Some(v) if v >= 1 && small_double_visit => true,
-                    _ => false,
-                }
+                matches!(visits[n], Some(v) if v >= 1 && small_double_visit)
             });
         ANSWER!(answer)
     }
 }
 
 impl Day12 {
+    #[instrument(name = "parse", level = "debug", skip_all)]
     fn parse_cave_graph(&self) -> Result<CaveGraph, Box<dyn Error>> {
-        let file = BufReader::new(File::open(&self.infile)?);
-
-        let mut edges = Vec::<(Node, Node)>::new();
-
-        for line_res in file.lines() {
-            let line = line_res
-                .map_err(|e| format!("invalid line: {}", e).to_string())?;
-
-            let mut nodes = line.split('-').map(str::trim);
-
-            match (
-                nodes.next().map(TryInto::try_into),
-                nodes.next().map(TryInto::try_into),
-                nodes.next(),
-            ) {
-                (Some(Ok(a)), Some(Ok(b)), None) => edges.push((a, b)),
-                _ => return Err(format!("invalid entry: {}", line).into()),
-            }
-        }
-
-        // TODO: ensure edges contains 1 start and 1 end
-
-        Ok(CaveGraph::from_edges(edges))
+        let file = input::load(self.infile())?;
+        let text = file.text()?;
+        let edges = parse::lines(text)
+            .map(|line| {
+                let (a, b) = line.split_once("-")?;
+                Ok((
+                    a.trim().parse_with(Node::try_from)?,
+                    b.trim().parse_with(Node::try_from)?,
+                ))
+            })
+            .collect::<Result<Vec<(Node, Node)>, ParseError>>()?;
+        CaveGraph::from_edges(edges)
     }
 }
@@ -1,9 +1,11 @@
 use std::{
-    collections::{HashMap, HashSet},
+    error::Error,
     fmt::{self, Debug},
     hash::{Hash, Hasher},
 };
 
+use crate::graph::Graph;
+
 #[derive(Clone, PartialEq, Eq)]
 pub enum Node {
     Start,
@@ -13,7 +15,7 @@ pub enum Node {
 }
 
 impl TryFrom<&str> for Node {
-    type Error = ();
+    type Error = &'static str;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         if value == "start" {
@@ -24,7 +26,7 @@ impl TryFrom<&str> for Node {
             match value.chars().next() {
                 Some('A'..='Z') => Ok(Self::Big(value.to_string())),
                 Some('a'..='z') => Ok(Self::Small(value.to_string())),
-                _ => Err(()),
+                _ => Err("invalid cave name"),
             }
         }
     }
@@ -44,10 +46,7 @@ impl Hash for Node {
 impl Node {
     #[inline]
     pub fn is_small(&self) -> bool {
-        match *self {
-            Node::Small(_) => true,
-            _ => false,
-        }
+        matches!(*self, Node::Small(_))
     }
 }
 
@@ -56,104 +55,80 @@ impl Debug for Node {
         match self {
             Node::Start => f.write_str("start"),
             Node::End => f.write_str("end"),
-            Node::Big(name) | Node::Small(name) => f.write_str(&name),
+            Node::Big(name) | Node::Small(name) => f.write_str(name),
         }
     }
 }
 
 pub struct CaveGraph {
-    pub nodes: Vec<Node>,
-    adj: Vec<Vec<usize>>,
+    graph: Graph<Node>,
     start_i: usize,
     end_i: usize,
 }
 
 impl CaveGraph {
-    pub fn from_edges(edges: Vec<(Node, Node)>) -> Self {
-        let node_set: HashSet<&Node> =
-            edges.iter().map(|(a, b)| [a, b]).flatten().collect();
-
-        let nodes: Vec<Node> = node_set.iter().map(|&n| n.clone()).collect();
-        let indexes: HashMap<&Node, usize> =
-            HashMap::from_iter(nodes.iter().enumerate().map(|(i, n)| (n, i)));
-        let start_i = *indexes.get(&Node::Start).unwrap();
-        let end_i = *indexes.get(&Node::End).unwrap();
-
-        let mut adj = vec![Vec::new(); nodes.len()];
-        for (a, b) in &edges {
-            adj[*indexes.get(a).unwrap()].push(*indexes.get(b).unwrap());
-            adj[*indexes.get(b).unwrap()].push(*indexes.get(a).unwrap());
+    pub fn from_edges(
+        edges: Vec<(Node, Node)>,
+    ) -> Result<Self, Box<dyn Error>> {
+        let mut graph = Graph::undirected();
+        for (a, b) in edges {
+            graph.add_edge(a, b);
         }
 
-        Self { nodes, adj, start_i, end_i }
+        let start_i = graph.index_of(&Node::Start).ok_or("no start cave")?;
+        let end_i = graph.index_of(&Node::End).ok_or("no end cave")?;
+
+        Ok(Self { graph, start_i, end_i })
     }
 
     pub fn find_all_paths_with(
         &self,
         skip_node: impl Copy + Fn(&mut Vec<Option<usize>>, bool, usize) -> bool,
     ) -> usize {
-        let mut path = Vec::with_capacity(self.nodes.len() * 2);
-        let mut visits = self
-            .nodes
+        let small = self
+            .graph
+            .nodes()
             .iter()
             .map(|n| if n.is_small() { Some(0_usize) } else { None })
             .collect();
-        let mut has_small_double_visit = false;
-        let mut count = 0;
-        self.find_all_paths_impl(
-            &mut path,
-            &mut visits,
-            &mut has_small_double_visit,
-            skip_node,
-            &mut count,
+        let mut visits = Visits { small, small_double_visit: false };
+        let start_i = self.start_i;
+        self.graph.count_walks(
             self.start_i,
-        );
-        count
+            self.end_i,
+            &mut visits,
+            |visits, n| {
+                if n == start_i
+                    || skip_node(
+                        &mut visits.small,
+                        visits.small_double_visit,
+                        n,
+                    )
+                {
+                    return false;
+                }
+                if let Some(v) = &mut visits.small[n] {
+                    *v += 1;
+                    if *v >= 2 {
+                        visits.small_double_visit = true;
+                    }
+                }
+                true
+            },
+            |visits, n| {
+                if let Some(v) = &mut visits.small[n] {
+                    *v -= 1;
+                    if *v >= 1 {
+                        visits.small_double_visit = false;
+                    }
+                }
+            },
+        )
     }
+}
 
-    fn find_all_paths_impl<'a>(
-        &'a self,
-        path: &mut Vec<usize>,
-        small_visits: &mut Vec<Option<usize>>,
-        has_small_double_visit: &mut bool,
-        skip_node: impl Copy + Fn(&mut Vec<Option<usize>>, bool, usize) -> bool,
-        count: &mut usize,
-        node: usize,
-    ) {
-        path.push(node);
-        if let Some(v) = &mut small_visits[node] {
-            *v += 1;
-            if *v >= 2 {
-                *has_small_double_visit = true;
-            }
-        }
-
-        for a in &self.adj[node] {
-            if *a == self.start_i
-                || skip_node(small_visits, *has_small_double_visit, *a)
-            {
-                continue;
-            }
-            if *a == self.end_i {
-                *count += 1;
-            } else {
-                self.find_all_paths_impl(
-                    path,
-                    small_visits,
-                    has_small_double_visit,
-                    skip_node,
-                    count,
-                    *a,
-                );
-            }
-        }
-
-        path.pop();
-        if let Some(v) = &mut small_visits[node] {
-            *v -= 1;
-            if *v >= 1 {
-                *has_small_double_visit = false;
-            }
-        }
-    }
+/// How often the walk through the caves is in each small cave.
+struct Visits {
+    small: Vec<Option<usize>>,
+    small_double_visit: bool,
 }
@@ -1,81 +1,171 @@
-use std::{
-    error::Error,
-    fs::File,
-    io::{BufRead, BufReader},
-    path::PathBuf,
-};
+use std::{error::Error, fmt::Write, path::PathBuf};
 
+use rand::{seq::SliceRandom, Rng};
 use structopt::StructOpt;
-
-use super::{todays_input, Day, PartResult, ANSWER};
+use tracing::instrument;
+
+use super::{
+    draw_letters, drawable_letters,
+    generate::InputRng,
+    params::{param, Param, ParamSpec},
+    snapshot::Session,
+    todays_input, Day, GenerateResult, PartResult, ANSWER,
+};
+use crate::{
+    animation::Recorder,
+    input,
+    parse::{self, ParseError, Span},
+};
 
 mod model;
-use self::model::{Instruction, Paper};
+use self::model::{Instruction, Paper, PAPER_PALETTE};
 
-todays_input!(INFILE_PATH);
+todays_input!(Day13);
+
+static PART1_FOLDS: Param<usize> =
+    param!("part1-folds", 1, "number of folds made before counting in part 1");
 
 #[derive(StructOpt)]
 pub struct Day13 {
-    #[structopt(default_value = &INFILE_PATH)]
-    infile: PathBuf,
+    infile: Option<PathBuf>,
 }
 
 impl Day for Day13 {
+    fn params(&self) -> Vec<&'static dyn ParamSpec> {
+        vec![&PART1_FOLDS]
+    }
+
+    fn generate(&self, rng: &mut InputRng, size: usize) -> GenerateResult {
+        const MESSAGE_LEN: usize = 8;
+
+        let letters: Vec<char> = drawable_letters().collect();
+        let message: String = (0..MESSAGE_LEN)
+            .map(|_| letters[rng.random_range(0..letters.len())])
+            .collect();
+        let rows = draw_letters(&message)?;
+
+        let mut dots: Vec<(usize, usize)> = rows
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.bytes()
+                    .enumerate()
+                    .filter(|(_, c)| *c == b'#')
+                    .map(move |(x, _)| (x, y))
+            })
+            .collect();
+        let (mut width, mut height) = (MESSAGE_LEN * 5, rows.len());
+
+        // Unfold the message: each unfolding doubles one side, and every dot
+        // stays, moves to its mirror image or is copied there. At least one
+        // dot has to reach the far side so the paper is wide enough to fold.
+        let (n_left, n_up) = (4 + size, 6 + size);
+        // Unfolded in this order, the paper is folded left and up in turns
+        // and then up a few more times, like the real inputs.
+        let mut folds = vec![false; n_up - n_left];
+        for _ in 0..n_left {
+            folds.extend([false, true]);
+        }
+        let mut instructions = Vec::new();
+        for &left in &folds {
+            let fold = if left { width } else { height };
+            let mut unfolded = Vec::with_capacity(dots.len() * 2);
+            for (i, &(x, y)) in dots.iter().enumerate() {
+                let mirror =
+                    if left { (2 * fold - x, y) } else { (x, 2 * fold - y) };
+                match rng.random_range(0..10) {
+                    _ if i == 0 => unfolded.push(mirror),
+                    0..=4 => unfolded.push((x, y)),
+                    5..=8 => unfolded.push(mirror),
+                    _ => unfolded.extend([(x, y), mirror]),
+                }
+            }
+            dots = unfolded;
+            if left {
+                width = 2 * width + 1;
+            } else {
+                height = 2 * height + 1;
+            }
+            instructions.push(if left {
+                format!("fold along x={}", fold)
+            } else {
+                format!("fold along y={}", fold)
+            });
+        }
+
+        dots.shuffle(rng);
+        let mut out = String::new();
+        for (x, y) in dots {
+            writeln!(out, "{},{}", x, y)?;
+        }
+        out.push('\n');
+        for instruction in instructions.iter().rev() {
+            writeln!(out, "{}", instruction)?;
+        }
+        Ok(out)
+    }
+
     fn part1(&self) -> PartResult {
         let (mut paper, instructions) = self.parse_instructions()?;
-
-        paper.fold(&instructions[0]);
+        let session = Session::start(module_path!(), 1, &mut paper)?;
+
+        let folds = PART1_FOLDS.get()?.min(instructions.len());
+        for (i, ins) in
+            instructions.iter().enumerate().take(folds).skip(session.start)
+        {
+            session.pause(i, &paper)?;
+            paper.fold(ins)?;
+        }
+        session.finish(folds, &paper)?;
 
         ANSWER!(paper.count_dots())
     }
 
     fn part2(&self) -> PartResult {
         let (mut paper, instructions) = self.parse_instructions()?;
-
-        for ins in &instructions {
-            paper.fold(ins);
+        let session = Session::start(module_path!(), 2, &mut paper)?;
+
+        let mut recorder =
+            Recorder::new("Day 13: Transparent Origami", PAPER_PALETTE);
+        recorder.frame(|| match session.start {
+            0 => paper.frame("unfolded".to_string()),
+            n => paper.frame(format!("resumed after fold {}", n)),
+        });
+        for (i, ins) in instructions.iter().enumerate().skip(session.start) {
+            session.pause(i, &paper)?;
+            paper.fold(ins)?;
+            recorder
+                .frame(|| paper.frame(format!("fold {}: {:?}", i + 1, ins)));
         }
+        session.finish(instructions.len(), &paper)?;
 
         ANSWER!(paper)
     }
 }
 
 impl Day13 {
+    #[instrument(name = "parse", level = "debug", skip_all)]
     fn parse_instructions(
         &self,
     ) -> Result<(Paper, Vec<Instruction>), Box<dyn Error>> {
-        let file = BufReader::new(File::open(&self.infile)?);
-        let mut line_results = file.lines();
-
-        let mut coords = Vec::<(usize, usize)>::new();
-
-        while let Some(line_res) = line_results.next() {
-            let line = line_res
-                .map_err(|e| format!("invalid line: {}", e).to_string())?;
-
-            if line.is_empty() {
-                break;
-            }
-
-            let mut xy = line.split(',');
-
-            match (xy.next(), xy.next(), xy.next()) {
-                (Some(x), Some(y), None) => {
-                    coords.push((x.trim().parse()?, y.trim().parse()?))
-                }
-                _ => return Err(format!("invalid line: {}", line).into()),
-            };
-        }
-
+        let file = input::load(self.infile())?;
+        let text = file.text()?;
+        let mut sections = parse::sections(text);
+
+        let dots = sections.next().ok_or("no dots")?;
+        let coords = dots
+            .lines()
+            .map(|line| {
+                let (x, y) = line.split_once(",")?;
+                Ok((x.trim().parse()?, y.trim().parse()?))
+            })
+            .collect::<Result<Vec<(usize, usize)>, ParseError>>()?;
         let paper = Paper::from_coords(coords)?;
 
-        let mut instructions = Vec::<Instruction>::new();
-
-        while let Some(line_res) = line_results.next() {
-            let line = line_res
-                .map_err(|e| format!("invalid line: {}", e).to_string())?;
-            instructions.push(line.parse()?);
-        }
+        let instructions = sections
+            .flat_map(Span::lines)
+            .map(Instruction::try_from)
+            .collect::<Result<_, _>>()?;
 
         Ok((paper, instructions))
     }
@@ -1,134 +1,155 @@
 use std::{
     error::Error,
-    fmt::{self, Debug, Display, Write},
-    str::FromStr,
+    fmt::{self, Debug},
 };
 
-pub struct Paper {
-    paper: Vec<Vec<bool>>,
-    width: usize,
-    height: usize,
-}
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    animation::{Frame, Palette, Swatch},
+    days::{snapshot::Snapshot, Answer},
+    grid::Grid,
+    parse::{ParseError, Span},
+};
+
+pub const PAPER_PALETTE: Palette =
+    &[Swatch::new('.', (60, 60, 60)), Swatch::new('#', (240, 240, 240))];
+
+pub struct Paper(Grid<bool>);
 
 impl Paper {
     pub fn from_coords(
         coords: Vec<(usize, usize)>,
     ) -> Result<Self, Box<dyn Error>> {
-        let (mut width, mut height) = coords
+        let (width, height) = coords
             .iter()
             .fold((0, 0), |acc, c| (acc.0.max(c.0), acc.1.max(c.1)));
-        width += 1;
-        height += 1;
 
-        let mut paper = vec![vec![false; width]; height];
-        for (x, y) in coords {
-            paper[y][x] = true;
+        let mut paper = Grid::new(width + 1, height + 1, false);
+        for c in coords {
+            paper[c] = true;
         }
 
-        Ok(Self { paper, width, height })
+        Ok(Self(paper))
     }
 
-    pub fn fold(&mut self, ins: &Instruction) {
+    /// Fold the paper in place, the part past the fold line onto the rest.
+    pub fn fold(&mut self, ins: &Instruction) -> Result<(), Box<dyn Error>> {
+        let paper = &mut self.0;
+        let (axis, fold, size, dimension) = match *ins {
+            Instruction::Up(fold) => ('y', fold, paper.height(), "high"),
+            Instruction::Left(fold) => ('x', fold, paper.width(), "wide"),
+        };
+        let problem = if fold >= size {
+            "is past the edge of"
+        } else if size - fold - 1 > fold {
+            "folds over the edge of"
+        } else {
+            ""
+        };
+        if !problem.is_empty() {
+            return Err(format!(
+                "fold along {}={} {} the paper, which is {} {}",
+                axis, fold, problem, size, dimension
+            )
+            .into());
+        }
+
         match *ins {
             Instruction::Up(fold) => {
-                for y in (fold + 1)..self.height {
-                    for x in 0..self.width {
-                        self.paper[fold - (y - fold)][x] |= self.paper[y][x];
+                let width = paper.width();
+                let (top, bottom) = paper.split_at_row_mut(fold);
+                let top = top.chunks_exact_mut(width).rev();
+                for (row, folded) in top.zip(bottom.chunks_exact(width).skip(1))
+                {
+                    for (dot, &folded) in row.iter_mut().zip(folded) {
+                        *dot |= folded;
                     }
                 }
-                self.height = fold;
+                paper.truncate_rows(fold);
             }
             Instruction::Left(fold) => {
-                for y in 0..self.height {
-                    for x in (fold + 1)..self.width {
-                        self.paper[y][fold - (x - fold)] |= self.paper[y][x];
+                for row in paper.rows_mut() {
+                    let (left, right) = row.split_at_mut(fold);
+                    for (dot, &folded) in left.iter_mut().rev().zip(&right[1..])
+                    {
+                        *dot |= folded;
                     }
                 }
-                self.width = fold;
+                paper.truncate_columns(fold);
             }
         }
+        Ok(())
     }
 
     pub fn count_dots(&self) -> usize {
-        let mut count = 0;
-        for y in 0..self.height {
-            for x in 0..self.width {
-                if self.paper[y][x] {
-                    count += 1;
-                }
-            }
-        }
-        count
+        self.0.iter().filter(|p| **p).count()
+    }
+
+    pub fn frame(&self, caption: String) -> Frame {
+        Frame::new(self.0.map(|&p| p as u8), caption)
     }
 }
 
 impl Debug for Paper {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        for y in 0..self.height {
-            let row_str: String = self.paper[y]
-                .iter()
-                .take(self.width)
-                .map(|p| if *p { '#' } else { '.' })
-                .collect();
-            f.write_str(&row_str)?;
-            f.write_char('\n')?;
-        }
-        Ok(())
+        writeln!(f, "{}", self.0.map(|p| if *p { '#' } else { '.' }))
     }
 }
 
-impl Display for Paper {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        for y in 0..self.height {
-            let row_str: String = self.paper[y]
-                .iter()
-                .take(self.width)
-                .map(|p| if *p { '#' } else { ' ' })
-                .collect();
-            f.write_str(&row_str)?;
-            f.write_char('\n')?;
-        }
+#[derive(Serialize, Deserialize)]
+pub struct PaperState {
+    dots: Vec<String>,
+}
+
+impl Snapshot for Paper {
+    type State = PaperState;
+
+    fn save(&self) -> Self::State {
+        let dots = self.0.map(|p| if *p { '#' } else { '.' }).to_string();
+        PaperState { dots: dots.lines().map(String::from).collect() }
+    }
+
+    fn restore(&mut self, state: Self::State) -> Result<(), Box<dyn Error>> {
+        self.0 = Grid::parse_chars(&state.dots.join("\n"), |c| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err(format!("invalid dot: {:?}", c)),
+        })?;
         Ok(())
     }
 }
 
+impl From<Paper> for Answer {
+    fn from(paper: Paper) -> Self {
+        Answer::Grid(
+            paper
+                .0
+                .rows()
+                .map(|row| {
+                    row.iter().map(|p| if *p { '#' } else { ' ' }).collect()
+                })
+                .collect(),
+        )
+    }
+}
+
 #[derive(Debug)]
 pub enum Instruction {
     Up(usize),
     Left(usize),
 }
 
-impl FromStr for Instruction {
-    type Err = Box<dyn Error>;
+impl TryFrom<Span<'_>> for Instruction {
+    type Error = ParseError;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        macro_rules! invalid_ins {
-            () => {
-                format!("invalid instruction: {}", s)
-            };
-        }
-
-        let location = if s.starts_with("fold along ") {
-            &s[11..]
-        } else {
-            return Err(invalid_ins!().into());
-        };
-
-        let mut axis_n = location.split('=');
-
-        let (axis, n) = match (axis_n.next(), axis_n.next(), axis_n.next()) {
-            (Some(axis), Some(n), None) => (axis.trim(), n.trim()),
-            _ => return Err(invalid_ins!().into()),
-        };
-
-        let value = n.parse().map_err(|_| invalid_ins!().to_string())?;
-
-        if axis == "x" {
-            Ok(Self::Left(value))
-        } else if axis == "y" {
-            Ok(Self::Up(value))
-        } else {
-            Err(invalid_ins!().into())
+    fn try_from(line: Span) -> Result<Self, Self::Error> {
+        let (axis, n) = line.strip_prefix("fold along ")?.key_value()?;
+        let value = n.parse()?;
+        match axis.text() {
+            "x" => Ok(Self::Left(value)),
+            "y" => Ok(Self::Up(value)),
+            _ => Err(axis.error(format!("invalid fold axis: {}", axis.text()))),
         }
     }
 }
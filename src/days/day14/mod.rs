@@ -1,20 +1,16 @@
-use std::{
-    error::Error,
-    fs::File,
-    io::{BufRead, BufReader},
-    path::PathBuf,
-};
+use std::{error::Error, path::PathBuf};
 
 use structopt::StructOpt;
+use tracing::{instrument, trace};
 
 use super::{todays_input, Day, PartResult, ANSWER};
+use crate::{input, parse};
 
-todays_input!(INFILE_PATH);
+todays_input!(Day14);
 
 #[derive(StructOpt)]
 pub struct Day14 {
-    #[structopt(default_value = &INFILE_PATH)]
-    infile: PathBuf,
+    infile: Option<PathBuf>,
 }
 
 impl Day for Day14 {
@@ -29,13 +25,13 @@ impl Day for Day14 {
 }
 
 impl Day14 {
+    #[instrument(name = "parse", level = "debug", skip_all)]
     fn parse(&self) -> Result<(), Box<dyn Error>> {
-        let file = BufReader::new(File::open(&self.infile)?);
+        let file = input::load(self.infile())?;
+        let text = file.text()?;
 
-        for line_res in file.lines() {
-            let line = line_res
-                .map_err(|e| format!("invalid line: {}", e).to_string())?;
-            println!("{}", line);
+        for line in parse::lines(text) {
+            trace!("{}", line.text());
         }
 
         Ok(())
@@ -1,26 +1,38 @@
-use std::{
-    error::Error,
-    fs::File,
-    io::{BufRead, BufReader},
-    path::PathBuf,
-};
+use std::{error::Error, fmt::Write, path::PathBuf};
 
+use rand::Rng;
 use structopt::StructOpt;
+use tracing::instrument;
 
-use super::{todays_input, Day, PartResult, ANSWER};
+use super::{
+    generate::InputRng, todays_input, Day, GenerateResult, PartResult, ANSWER,
+};
+use crate::{input, parse};
 
 mod model;
 use self::model::Command;
 
-todays_input!(INFILE_PATH);
+todays_input!(Day2);
 
 #[derive(StructOpt)]
 pub struct Day2 {
-    #[structopt(default_value = &INFILE_PATH)]
-    infile: PathBuf,
+    infile: Option<PathBuf>,
 }
 
 impl Day for Day2 {
+    fn generate(&self, rng: &mut InputRng, size: usize) -> GenerateResult {
+        let mut out = String::new();
+        for _ in 0..1000 * size {
+            let direction = match rng.random_range(0..10) {
+                0..=4 => "forward",
+                5..=7 => "down",
+                _ => "up",
+            };
+            writeln!(out, "{} {}", direction, rng.random_range(1..=9))?;
+        }
+        Ok(out)
+    }
+
     fn part1(&self) -> PartResult {
         let commands = self.parse_commands()?;
         let mut x = 0_isize;
@@ -59,14 +71,12 @@ impl Day for Day2 {
 }
 
 impl Day2 {
+    #[instrument(name = "parse", level = "debug", skip_all)]
     fn parse_commands(&self) -> Result<Vec<Command>, Box<dyn Error>> {
-        let file = BufReader::new(File::open(&self.infile)?);
-        let commands = file
-            .lines()
-            .map(|l| match l {
-                Ok(s) => s.parse(),
-                _ => Err("invalid line".into()),
-            })
+        let file = input::load(self.infile())?;
+        let text = file.text()?;
+        let commands = parse::lines(text)
+            .map(Command::try_from)
             .collect::<Result<Vec<_>, _>>()?;
         Ok(commands)
     }
@@ -1,4 +1,4 @@
-use std::{error::Error, str::FromStr};
+use crate::parse::{ParseError, Span};
 
 #[derive(PartialEq)]
 pub enum Command {
@@ -7,18 +7,20 @@ pub enum Command {
     Down(isize),
 }
 
-impl FromStr for Command {
-    type Err = Box<dyn Error>;
+impl TryFrom<Span<'_>> for Command {
+    type Error = ParseError;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let i = s.find(char::is_whitespace).unwrap();
-        let direction = &s[..i];
-        let magnitude = (s.as_bytes()[i + 1] - '0' as u8) as isize;
-        match direction {
+    fn try_from(line: Span) -> Result<Self, Self::Error> {
+        let (direction, magnitude) = line.split_once(" ")?;
+        let magnitude = magnitude.parse()?;
+        match direction.text() {
             "forward" => Ok(Command::Forward(magnitude)),
             "up" => Ok(Command::Up(magnitude)),
             "down" => Ok(Command::Down(magnitude)),
-            _ => Err(format!("direction not recognized: {}", direction).into()),
+            _ => Err(direction.error(format!(
+                "direction not recognized: {}",
+                direction.text()
+            ))),
         }
     }
 }
@@ -1,36 +1,65 @@
 use std::{
-    error::Error,
-    fs::File,
-    io::{BufRead, BufReader},
-    ops::BitAnd,
-    path::PathBuf,
+    collections::HashSet, error::Error, fmt::Write, ops::BitAnd, path::PathBuf,
 };
 
+use rand::Rng;
 use structopt::StructOpt;
+use tracing::instrument;
 
-use super::{todays_input, Day, PartResult, ANSWER};
+use super::{
+    explain::{self, Fact},
+    generate::InputRng,
+    params::{param, Param, ParamSpec},
+    todays_input, Day, GenerateResult, PartResult, ANSWER,
+};
+use crate::{input, parse};
 
-todays_input!(INFILE_PATH);
+todays_input!(Day3);
 
-const N_BITS: usize = 12;
+static N_BITS: Param<usize> =
+    param!("bits", 12, "number of bits in each diagnostic report value");
 
 #[derive(StructOpt)]
 pub struct Day3 {
-    #[structopt(default_value = &INFILE_PATH)]
-    infile: PathBuf,
+    infile: Option<PathBuf>,
 }
 
 impl Day for Day3 {
+    fn params(&self) -> Vec<&'static dyn ParamSpec> {
+        vec![&N_BITS]
+    }
+
+    fn generate(&self, rng: &mut InputRng, size: usize) -> GenerateResult {
+        let n_bits = self.n_bits()?;
+        let max = usize::MAX >> (usize::BITS as usize - n_bits);
+        // Values are distinct so that the ratings can always be isolated.
+        let n_values = (1000 * size).min(max.saturating_add(1));
+        let mut seen = HashSet::with_capacity(n_values);
+        let mut values = Vec::with_capacity(n_values);
+        while values.len() < n_values {
+            let value = rng.random_range(0..=max);
+            if seen.insert(value) {
+                values.push(value);
+            }
+        }
+
+        let mut out = String::new();
+        for value in values {
+            writeln!(out, "{:0width$b}", value, width = n_bits)?;
+        }
+        Ok(out)
+    }
+
     fn part1(&self) -> PartResult {
         let report = self.parse_report()?;
         let mut n_lines = 0_usize;
-        let mut gamma_counts = vec![0_usize; N_BITS];
+        let mut gamma_counts = vec![0_usize; self.n_bits()?];
 
         for value in &report {
             let mut bit_index = 1;
-            for index in 0..N_BITS {
+            for count in gamma_counts.iter_mut() {
                 if value.bitand(bit_index) > 0 {
-                    gamma_counts[index] += 1;
+                    *count += 1;
                 }
                 bit_index <<= 1;
             }
@@ -51,6 +80,12 @@ impl Day for Day3 {
             }
         }
 
+        explain::fact(|| {
+            let n_bits = gamma_counts.len();
+            Fact::new("rates")
+                .with("gamma", bits(gamma, n_bits))
+                .with("epsilon", bits(epsilon, n_bits))
+        });
         ANSWER!(gamma * epsilon)
     }
 
@@ -58,7 +93,7 @@ impl Day for Day3 {
         let mut report = self.parse_report()?;
         report.sort();
 
-        let mut bit_index = 1_usize << (N_BITS - 1);
+        let mut bit_index = 1_usize << (self.n_bits()? - 1);
         let mut oxy_window = report.as_slice();
         let mut co2_window = report.as_slice();
         while bit_index > 0 && (oxy_window.len() > 1 || co2_window.len() > 1) {
@@ -79,9 +114,12 @@ impl Day for Day3 {
                     .iter()
                     .position(|&v| v & bit_index == bit_index)
                     .unwrap_or(co2_window.len());
-                if co2_part_i > co2_window.len() / 2 {
+                // When every value has the same bit there is no less common
+                // one, so they are all kept.
+                let mixed = co2_part_i != 0 && co2_part_i != co2_window.len();
+                if mixed && co2_part_i > co2_window.len() / 2 {
                     co2_window = &co2_window[co2_part_i..];
-                } else {
+                } else if mixed {
                     co2_window = &co2_window[..co2_part_i];
                 }
             }
@@ -101,23 +139,51 @@ impl Day for Day3 {
             _ => return Err("failed to isolate one value for CO2 rate".into()),
         };
 
+        explain::fact(|| {
+            let n_bits = self.n_bits().unwrap_or_default();
+            Fact::new("ratings")
+                .with("oxygen", bits(oxy_answer, n_bits))
+                .with("co2", bits(co2_answer, n_bits))
+        });
         ANSWER!(oxy_answer * co2_answer)
     }
 }
 
 impl Day3 {
-    fn parse_report(&self) -> Result<Vec<usize>, Box<dyn Error>> {
-        let mut report = Vec::new();
-
-        for line_res in BufReader::new(File::open(&self.infile)?).lines() {
-            let line = line_res
-                .map_err(|e| format!("invalid line: {}", e).to_string())?;
-            match usize::from_str_radix(&line, 2) {
-                Ok(value) => report.push(value),
-                _ => return Err(format!("invalid value: {}", line).into()),
-            }
+    fn n_bits(&self) -> Result<usize, Box<dyn Error>> {
+        let n_bits = N_BITS.get()?;
+        if n_bits == 0 || n_bits > usize::BITS as usize {
+            return Err(format!("invalid number of bits: {}", n_bits).into());
         }
+        Ok(n_bits)
+    }
 
+    #[instrument(name = "parse", level = "debug", skip_all)]
+    fn parse_report(&self) -> Result<Vec<usize>, Box<dyn Error>> {
+        let file = input::load(self.infile())?;
+        let text = file.text()?;
+        let report = parse::lines(text)
+            .map(|line| line.parse_with(|s| usize::from_str_radix(s, 2)))
+            .collect::<Result<_, _>>()?;
         Ok(report)
     }
 }
+
+/// A value in binary as the report has it, and in decimal.
+fn bits(value: usize, n_bits: usize) -> String {
+    format!("{:0width$b} ({})", value, value, width = n_bits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::Answer;
+
+    #[test]
+    fn co2_rating_keeps_values_that_share_a_bit() {
+        // The example's values have 5 bits, so with the default 12 bits
+        // every value starts with the same 7 zeros.
+        let day = Day3 { infile: Some(module_dir().join("test-input.txt")) };
+        assert_eq!(day.part2().unwrap(), Answer::from(230_usize));
+    }
+}
@@ -1,39 +1,68 @@
-use std::{
-    error::Error,
-    fs::File,
-    io::{BufRead, BufReader},
-    path::PathBuf,
-};
+use std::{error::Error, fmt::Write, path::PathBuf};
 
+use rand::seq::{index, SliceRandom};
 use structopt::StructOpt;
+use tracing::instrument;
 
-use super::{todays_input, Day, PartResult, ANSWER};
+use super::{
+    explain::{self, Fact},
+    generate::InputRng,
+    snapshot::Session,
+    todays_input, Day, GenerateResult, PartResult, ANSWER,
+};
+use crate::{animation::Recorder, input, parse};
 
 mod model;
-use self::model::Board;
+use self::model::{bingo_frame, Board, BINGO_PALETTE};
 
-todays_input!(INFILE_PATH);
+todays_input!(Day4);
 
 #[derive(StructOpt)]
 pub struct Day4 {
-    #[structopt(default_value = &INFILE_PATH)]
-    infile: PathBuf,
+    infile: Option<PathBuf>,
 }
 
 impl Day for Day4 {
+    fn generate(&self, rng: &mut InputRng, size: usize) -> GenerateResult {
+        let n_numbers = 100 * size;
+        let mut numbers: Vec<usize> = (0..n_numbers).collect();
+        numbers.shuffle(rng);
+
+        let mut out =
+            numbers.iter().map(usize::to_string).collect::<Vec<_>>().join(",");
+        out.push('\n');
+
+        for _ in 0..100 * size {
+            let board = index::sample(rng, n_numbers, 25).into_vec();
+            out.push('\n');
+            for row in board.chunks(5) {
+                let row: Vec<_> =
+                    row.iter().map(|n| format!("{:2}", n)).collect();
+                writeln!(out, "{}", row.join(" "))?;
+            }
+        }
+        Ok(out)
+    }
+
     fn part1(&self) -> PartResult {
         let (numbers, mut boards) = self.parse_bingo()?;
+        let session = Session::start(module_path!(), 1, &mut boards)?;
 
         let mut maybe_answer = None;
+        let mut drawn = session.start;
 
-        'draw_num: for num in &numbers {
-            for b in &mut boards {
+        'draw_num: for (draw, num) in numbers.iter().enumerate().skip(drawn) {
+            session.pause(draw, &boards)?;
+            drawn = draw + 1;
+            for (i, b) in boards.iter_mut().enumerate() {
                 if b.mark_check(*num) {
+                    explain::fact(|| winner("first winner", i, b, *num, drawn));
                     maybe_answer = Some(b.sum_unmarked() * num);
                     break 'draw_num;
                 }
             }
         }
+        session.finish(drawn, &boards)?;
 
         match maybe_answer {
             Some(answer) => ANSWER!(answer),
@@ -43,12 +72,18 @@ impl Day for Day4 {
 
     fn part2(&self) -> PartResult {
         let (numbers, mut boards) = self.parse_bingo()?;
+        let session = Session::start(module_path!(), 2, &mut boards)?;
 
         let num_boards = boards.len();
-        let mut num_wins = 0;
-        let mut board_wins = vec![false; num_boards];
+        let mut board_wins: Vec<_> =
+            boards.iter().map(Board::has_won).collect();
+        let mut num_wins = board_wins.iter().filter(|w| **w).count();
         let mut maybe_answer = None;
-        'draw_num: for num in &numbers {
+        let mut drawn = session.start;
+        let mut recorder = Recorder::new("Day 4: Giant Squid", BINGO_PALETTE);
+        for (draw, num) in numbers.iter().enumerate().skip(drawn) {
+            session.pause(draw, &boards)?;
+            drawn = draw + 1;
             for (i, b) in boards.iter_mut().enumerate() {
                 if board_wins[i] {
                     continue;
@@ -57,12 +92,23 @@ impl Day for Day4 {
                     board_wins[i] = true;
                     num_wins += 1;
                     if num_wins == num_boards {
+                        explain::fact(|| {
+                            winner("last winner", i, b, *num, drawn)
+                        });
                         maybe_answer = Some(b.sum_unmarked() * num);
-                        break 'draw_num;
+                        break;
                     }
                 }
             }
+            recorder.frame(|| {
+                let caption = format!("drew {}: {} boards won", num, num_wins);
+                bingo_frame(&boards, &board_wins, caption)
+            });
+            if maybe_answer.is_some() {
+                break;
+            }
         }
+        session.finish(drawn, &boards)?;
 
         match maybe_answer {
             Some(answer) => ANSWER!(answer),
@@ -71,37 +117,33 @@ impl Day for Day4 {
     }
 }
 
+/// The board that won and the draw it won on.
+fn winner(
+    subject: &str,
+    i: usize,
+    board: &Board,
+    num: usize,
+    drawn: usize,
+) -> Fact {
+    Fact::new(subject)
+        .with("board", i + 1)
+        .with("draw", num)
+        .with("numbers_drawn", drawn)
+        .with("unmarked_sum", board.sum_unmarked())
+}
+
 impl Day4 {
+    #[instrument(name = "parse", level = "debug", skip_all)]
     fn parse_bingo(&self) -> Result<(Vec<usize>, Vec<Board>), Box<dyn Error>> {
-        let mut lines = BufReader::new(File::open(&self.infile)?).lines();
+        let file = input::load(self.infile())?;
+        let text = file.text()?;
+        let mut sections = parse::sections(text);
 
-        let num_str = match lines.next() {
-            Some(num_str_res) => match num_str_res {
-                Ok(num_str) => num_str,
-                Err(e) => return Err(format!("invalid numbers: {}", e).into()),
-            },
+        let numbers = match sections.next() {
+            Some(numbers) => numbers.trim().delimited(",")?,
             None => return Err("no numbers".into()),
         };
-
-        let maybe_nums: Result<Vec<usize>, _> =
-            num_str.split(',').map(str::parse).collect();
-        let numbers = match maybe_nums {
-            Ok(nums) => nums,
-            _ => return Err(format!("invalid numbers: {}", num_str).into()),
-        };
-
-        let mut boards: Vec<Board> = Vec::new();
-        let mut b = Vec::with_capacity(5);
-        while let Some(_spacer) = lines.next() {
-            b.clear();
-            for _ in 0..5 {
-                match lines.next() {
-                    Some(Ok(r)) => b.push(r),
-                    _ => return Err("invalid board".into()),
-                }
-            }
-            boards.push(b[..5].try_into()?);
-        }
+        let boards = sections.map(Board::try_from).collect::<Result<_, _>>()?;
 
         Ok((numbers, boards))
     }
@@ -1,105 +1,175 @@
 use std::{
     error::Error,
     fmt::{self, Debug},
-    ops::{Deref, DerefMut},
 };
 
-#[derive(Default)]
-pub struct Board([BoardRow; 5]);
+use serde::{Deserialize, Serialize};
 
-impl Board {
-    pub fn mark_check(&mut self, num: usize) -> bool {
-        let mut is_win = false;
-        for row in &mut self.0 {
-            let mut row_is_win = true;
-            for spot in row.iter_mut() {
-                row_is_win &= spot.mark_if_matching(num);
-            }
-            is_win |= row_is_win;
-        }
-        if is_win {
-            return true;
-        }
+use crate::{
+    animation::{Frame, Palette, Swatch},
+    days::snapshot::Snapshot,
+    grid::Grid,
+    parse::{ParseError, Span},
+    style::{Style, THEME},
+};
 
-        for spot_i in 0..5 {
-            if self.0.iter().all(|r| r[spot_i].is_marked()) {
-                return true;
+const BOARD_SIZE: usize = 5;
+
+/// How many boards are drawn side by side in a frame.
+const BOARDS_PER_ROW: usize = 10;
+
+pub const BINGO_PALETTE: Palette = &[
+    Swatch::new(' ', (0, 0, 0)),
+    Swatch::new('.', (90, 90, 90)),
+    Swatch::new('#', (230, 190, 60)),
+    Swatch::new('@', (90, 220, 120)),
+];
+
+/// All boards laid out in rows, with the marked spots of boards that have
+/// already won drawn differently.
+pub fn bingo_frame(
+    boards: &[Board],
+    board_wins: &[bool],
+    caption: String,
+) -> Frame {
+    let cell = BOARD_SIZE + 1;
+    let width = boards.len().min(BOARDS_PER_ROW) * cell;
+    let height = boards.len().div_ceil(BOARDS_PER_ROW) * cell;
+    let cells = Grid::from_fn(
+        width.saturating_sub(1),
+        height.saturating_sub(1),
+        |x, y| {
+            let i = y / cell * BOARDS_PER_ROW + x / cell;
+            let pos = (x % cell, y % cell);
+            match boards.get(i).and_then(|b| b.0.get(pos)) {
+                None => 0,
+                Some(spot) if !spot.is_marked() => 1,
+                Some(_) if !board_wins[i] => 2,
+                Some(_) => 3,
             }
-        }
+        },
+    );
+    Frame::new(cells, caption)
+}
+
+pub struct Board(Grid<BoardSpot>);
+
+impl Board {
+    /// Mark `num` and tell whether that completed its row or column.
+    pub fn mark_check(&mut self, num: usize) -> bool {
+        let board = &mut self.0;
+        let (x, y) = match board.iter().position(|spot| spot.value == num) {
+            Some(i) => (i % board.width(), i / board.width()),
+            None => return false,
+        };
+        board[(x, y)].marked = true;
+        board.row(y).iter().all(BoardSpot::is_marked)
+            || board.column(x).all(BoardSpot::is_marked)
+    }
 
-        false
+    pub fn has_won(&self) -> bool {
+        let board = &self.0;
+        (0..board.height())
+            .any(|y| board.row(y).iter().all(BoardSpot::is_marked))
+            || (0..board.width())
+                .any(|x| board.column(x).all(BoardSpot::is_marked))
     }
 
     pub fn sum_unmarked(&self) -> usize {
-        self.0
-            .iter()
-            .map(|row| row.iter())
-            .flatten()
-            .filter_map(BoardSpot::unmarked)
-            .sum()
+        self.0.iter().filter_map(BoardSpot::unmarked).sum()
     }
 }
 
-impl TryFrom<&[String]> for Board {
-    type Error = Box<dyn Error>;
-
-    fn try_from(lines: &[String]) -> Result<Self, Self::Error> {
-        let mut this = Self::default();
-        for i in 0..5 {
-            this.0[i] = lines[i].as_str().try_into()?;
-        }
-        Ok(this)
-    }
+#[derive(Serialize, Deserialize)]
+pub struct BoardsState {
+    marks: Vec<Vec<String>>,
 }
 
-impl Debug for Board {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        for row in &self.0 {
-            f.write_fmt(format_args!("{:?}", row))?;
-        }
-        Ok(())
+impl Snapshot for Vec<Board> {
+    type State = BoardsState;
+
+    fn save(&self) -> Self::State {
+        let marks = self
+            .iter()
+            .map(|b| {
+                let marks = b.0.map(|s| if s.marked { '#' } else { '.' });
+                marks.to_string().lines().map(String::from).collect()
+            })
+            .collect();
+        BoardsState { marks }
     }
-}
 
-#[derive(Default)]
-struct BoardRow([BoardSpot; 5]);
-
-impl TryFrom<&str> for BoardRow {
-    type Error = Box<dyn Error>;
-
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let mut this = Self::default();
-        let mut cols = value.split_ascii_whitespace();
-        for i in 0..5 {
-            match cols.next().map(str::parse) {
-                Some(Ok(v)) => this.0[i] = BoardSpot::new(v),
-                _ => return Err(format!("invalid board row: {}", value).into()),
+    fn restore(&mut self, state: Self::State) -> Result<(), Box<dyn Error>> {
+        if state.marks.len() != self.len() {
+            return Err(format!(
+                "the snapshot has {} boards, the input {}",
+                state.marks.len(),
+                self.len()
+            )
+            .into());
+        }
+        for (board, marks) in self.iter_mut().zip(state.marks) {
+            let marks = Grid::parse_chars(&marks.join("\n"), |c| match c {
+                '#' => Ok(true),
+                '.' => Ok(false),
+                _ => Err(format!("invalid mark: {:?}", c)),
+            })?;
+            if (marks.width(), marks.height()) != (BOARD_SIZE, BOARD_SIZE) {
+                return Err("board marks must be 5x5".into());
+            }
+            for (spot, marked) in board.0.iter_mut().zip(marks.iter()) {
+                spot.marked = *marked;
             }
         }
-        Ok(this)
+        Ok(())
     }
 }
 
-impl Deref for BoardRow {
-    type Target = [BoardSpot; 5];
+impl TryFrom<Span<'_>> for Board {
+    type Error = ParseError;
 
-    fn deref(&self) -> &Self::Target {
-        &self.0
+    fn try_from(section: Span) -> Result<Self, Self::Error> {
+        let mut spots = Vec::with_capacity(BOARD_SIZE * BOARD_SIZE);
+        let mut n_rows = 0;
+        for line in section.lines() {
+            parse_board_row(line, &mut spots)?;
+            n_rows += 1;
+        }
+        if n_rows != BOARD_SIZE {
+            return Err(section
+                .error(format!("invalid number of board rows: {}", n_rows)));
+        }
+        let board = Grid::from_cells(BOARD_SIZE, spots)
+            .map_err(|e| section.error(e))?;
+        Ok(Self(board))
     }
 }
 
-impl DerefMut for BoardRow {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+fn parse_board_row(
+    line: Span,
+    spots: &mut Vec<BoardSpot>,
+) -> Result<(), ParseError> {
+    let start = spots.len();
+    for col in line.split_whitespace() {
+        spots.push(BoardSpot::new(col.parse()?));
     }
+    if spots.len() - start != BOARD_SIZE {
+        return Err(line.error(format!(
+            "invalid board row: expected {} numbers",
+            BOARD_SIZE
+        )));
+    }
+    Ok(())
 }
 
-impl Debug for BoardRow {
+impl Debug for Board {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        for spot in &self.0 {
-            f.write_fmt(format_args!("{:?} ", spot))?;
+        for row in self.0.rows() {
+            for spot in row {
+                f.write_fmt(format_args!("{:?} ", spot))?;
+            }
+            f.write_str("\n")?;
         }
-        println!("");
         Ok(())
     }
 }
@@ -121,16 +191,6 @@ impl BoardSpot {
         self.marked
     }
 
-    #[inline]
-    fn mark_if_matching(&mut self, num: usize) -> bool {
-        if self.value == num {
-            self.marked = true;
-            true
-        } else {
-            self.marked
-        }
-    }
-
     #[inline]
     fn unmarked(&self) -> Option<usize> {
         if self.marked {
@@ -143,13 +203,7 @@ impl BoardSpot {
 
 impl Debug for BoardSpot {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        if self.marked {
-            f.write_str("\x1b[33m")?;
-        }
-        f.write_fmt(format_args!("{:2}", self.value))?;
-        if self.marked {
-            f.write_str("\x1b[0m")?;
-        }
-        Ok(())
+        let style = if self.marked { THEME.highlight } else { Style::plain() };
+        write!(f, "{}", style.paint(format_args!("{:2}", self.value)))
     }
 }
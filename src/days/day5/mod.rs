@@ -1,42 +1,78 @@
-use std::{
-    cmp::max,
-    error::Error,
-    fs::File,
-    io::{BufRead, BufReader},
-    path::PathBuf,
-};
+use std::{cmp::max, error::Error, fmt::Write, path::PathBuf};
 
+use rand::Rng;
 use structopt::StructOpt;
+use tracing::instrument;
 
-use super::{todays_input, Day, PartResult, ANSWER};
+use super::{
+    generate::InputRng, todays_input, Day, GenerateResult, PartResult, ANSWER,
+};
+use crate::{
+    animation::{Frame, Palette, Recorder, Swatch},
+    grid::Grid,
+    input, parse,
+};
 
 mod model;
 use self::model::Vent;
 
-todays_input!(INFILE_PATH);
+todays_input!(Day5);
+
+/// Roughly how many frames drawing all the vents is animated in.
+const N_FRAMES: usize = 50;
+
+const VENT_PALETTE: Palette = &[
+    Swatch::new('.', (20, 20, 40)),
+    Swatch::new('1', (60, 110, 200)),
+    Swatch::new('2', (240, 160, 60)),
+    Swatch::new('#', (255, 80, 60)),
+];
 
 #[derive(StructOpt)]
 pub struct Day5 {
-    #[structopt(default_value = &INFILE_PATH)]
-    infile: PathBuf,
+    infile: Option<PathBuf>,
 }
 
 impl Day for Day5 {
+    fn generate(&self, rng: &mut InputRng, size: usize) -> GenerateResult {
+        const SIDE: i64 = 1000;
+
+        let mut out = String::new();
+        for _ in 0..500 * size {
+            let (x1, y1) =
+                (rng.random_range(0..SIDE), rng.random_range(0..SIDE));
+            let (x2, y2) = match rng.random_range(0..3) {
+                0 => (rng.random_range(0..SIDE), y1),
+                1 => (x1, rng.random_range(0..SIDE)),
+                _ => {
+                    let dx = if rng.random_bool(0.5) { 1 } else { -1 };
+                    let dy = if rng.random_bool(0.5) { 1 } else { -1 };
+                    let room_x = if dx > 0 { SIDE - 1 - x1 } else { x1 };
+                    let room_y = if dy > 0 { SIDE - 1 - y1 } else { y1 };
+                    let len = rng.random_range(0..=room_x.min(room_y));
+                    (x1 + dx * len, y1 + dy * len)
+                }
+            };
+            writeln!(out, "{},{} -> {},{}", x1, y1, x2, y2)?;
+        }
+        Ok(out)
+    }
+
     fn part1(&self) -> PartResult {
         let (width, height, vents) = self.parse_vents()?;
 
-        let mut diagram: Vec<Vec<usize>> = vec![vec![0_usize; width]; height];
+        let mut diagram = Grid::new(width, height, 0_usize);
 
         for ls in &vents {
             match *ls {
                 Vent::Horizontal { x1, x2, y } => {
-                    for point in &mut diagram[y][x1..=x2] {
+                    for point in &mut diagram.row_mut(y)[x1..=x2] {
                         *point += 1;
                     }
                 }
                 Vent::Vertical { x, y1, y2 } => {
-                    for row in &mut diagram[y1..=y2] {
-                        row[x] += 1;
+                    for y in y1..=y2 {
+                        diagram[(x, y)] += 1;
                     }
                 }
                 Vent::Diagonal { .. } => (),
@@ -49,18 +85,24 @@ impl Day for Day5 {
     fn part2(&self) -> PartResult {
         let (width, height, vents) = self.parse_vents()?;
 
-        let mut diagram: Vec<Vec<usize>> = vec![vec![0_usize; width]; height];
+        let mut diagram = Grid::new(width, height, 0_usize);
+        let mut recorder =
+            Recorder::new("Day 5: Hydrothermal Venture", VENT_PALETTE);
+        let frame_every = vents.len().div_ceil(N_FRAMES).max(1);
 
-        for ls in &vents {
+        for (i, ls) in vents.iter().enumerate() {
+            if i % frame_every == 0 {
+                recorder.frame(|| vent_frame(&diagram, i));
+            }
             match *ls {
                 Vent::Horizontal { x1, x2, y } => {
-                    for point in &mut diagram[y][x1..=x2] {
+                    for point in &mut diagram.row_mut(y)[x1..=x2] {
                         *point += 1;
                     }
                 }
                 Vent::Vertical { x, y1, y2 } => {
-                    for row in &mut diagram[y1..=y2] {
-                        row[x] += 1;
+                    for y in y1..=y2 {
+                        diagram[(x, y)] += 1;
                     }
                 }
                 Vent::Diagonal { x1, x2, y1, y2 } => {
@@ -69,33 +111,34 @@ impl Day for Day5 {
                     let (mut y, mut x) = (y1 as isize, x1 as isize);
                     let range = (y2 as isize - y1 as isize) * dy;
                     for _ in 0..=range {
-                        diagram[y as usize][x as usize] += 1;
+                        diagram[(x as usize, y as usize)] += 1;
                         y += dy;
                         x += dx;
                     }
                 }
             }
         }
+        recorder.frame(|| vent_frame(&diagram, vents.len()));
 
         ANSWER!(count_overlapping_vents(&diagram))
     }
 }
 
 impl Day5 {
+    #[instrument(name = "parse", level = "debug", skip_all)]
     fn parse_vents(&self) -> Result<(usize, usize, Vec<Vent>), Box<dyn Error>> {
-        let file = BufReader::new(File::open(&self.infile)?);
+        let file = input::load(self.infile())?;
+        let text = file.text()?;
 
         let mut vents: Vec<Vent> = Vec::new();
 
         let mut width = 0;
         let mut height = 0;
-        for line_res in file.lines() {
-            let vent = line_res
-                .map_err(|e| format!("invalid line: {}", e).to_string())?
-                .parse()?;
+        for line in parse::lines(text) {
+            let vent = Vent::try_from(line)?;
 
-            match &vent {
-                &Vent::Horizontal { x1, x2, y } => {
+            match vent {
+                Vent::Horizontal { x1, x2, y } => {
                     let max_x = max(x1, x2);
                     if max_x > width {
                         width = max_x;
@@ -104,7 +147,7 @@ impl Day5 {
                         height = y
                     }
                 }
-                &Vent::Vertical { x, y1, y2 } => {
+                Vent::Vertical { x, y1, y2 } => {
                     let max_y = max(y1, y2);
                     if max_y > height {
                         height = max_y;
@@ -113,7 +156,7 @@ impl Day5 {
                         width = x
                     }
                 }
-                &Vent::Diagonal { x1, x2, y1, y2 } => {
+                Vent::Diagonal { x1, x2, y1, y2 } => {
                     let max_y = max(y1, y2);
                     if max_y > height {
                         height = max_y;
@@ -132,6 +175,17 @@ impl Day5 {
     }
 }
 
-fn count_overlapping_vents(diagram: &Vec<Vec<usize>>) -> usize {
-    diagram.iter().flatten().filter(|p| **p >= 2).count()
+fn vent_frame(diagram: &Grid<usize>, n_vents: usize) -> Frame {
+    Frame::new(
+        diagram.map(|&n| n.min(3) as u8),
+        format!(
+            "{} vents, {} overlaps",
+            n_vents,
+            count_overlapping_vents(diagram)
+        ),
+    )
+}
+
+fn count_overlapping_vents(diagram: &Grid<usize>) -> usize {
+    diagram.iter().filter(|p| **p >= 2).count()
 }
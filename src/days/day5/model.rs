@@ -1,8 +1,6 @@
-use std::{
-    error::Error,
-    fmt::{self, Formatter},
-    str::FromStr,
-};
+use std::fmt::{self, Formatter};
+
+use crate::parse::{ParseError, Span};
 
 trait OrderedVars<T: PartialOrd> {
     fn ordered(self) -> (T, T);
@@ -26,21 +24,13 @@ pub enum Vent {
     Diagonal { x1: usize, x2: usize, y1: usize, y2: usize },
 }
 
-impl FromStr for Vent {
-    type Err = Box<dyn Error>;
-
-    fn from_str(value: &str) -> Result<Self, Self::Err> {
-        let mut points = value.split(" -> ");
-
-        let mut p = points.next().unwrap();
-        let mut i = p.find(',').unwrap();
-        let x1 = p[..i].parse().unwrap();
-        let y1 = p[i + 1..].parse().unwrap();
+impl TryFrom<Span<'_>> for Vent {
+    type Error = ParseError;
 
-        p = points.next().unwrap();
-        i = p.find(',').unwrap();
-        let x2 = p[..i].parse().unwrap();
-        let y2 = p[i + 1..].parse().unwrap();
+    fn try_from(line: Span) -> Result<Self, Self::Error> {
+        let (start, end) = line.rule()?;
+        let (x1, y1) = parse_point(start)?;
+        let (x2, y2) = parse_point(end)?;
 
         if y1 == y2 {
             let (x1, x2) = (x1, x2).ordered();
@@ -54,6 +44,11 @@ impl FromStr for Vent {
     }
 }
 
+fn parse_point(point: Span) -> Result<(usize, usize), ParseError> {
+    let (x, y) = point.split_once(",")?;
+    Ok((x.trim().parse()?, y.trim().parse()?))
+}
+
 impl fmt::Display for Vent {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match *self {
@@ -1,53 +1,77 @@
-use std::{error::Error, fs::File, io::Read, path::PathBuf};
+use std::{error::Error, path::PathBuf};
 
+use rand::Rng;
 use structopt::StructOpt;
 
-use super::{todays_input, Day, PartResult, ANSWER};
+use super::{
+    generate::InputRng,
+    params::{param, Param, ParamSpec},
+    snapshot::Session,
+    todays_input, Day, GenerateResult, PartResult, ANSWER,
+};
+use crate::{input, parse::Span};
 
-todays_input!(INFILE_PATH);
+mod model;
+use self::model::School;
+
+todays_input!(Day6);
+
+static PART1_DAYS: Param<usize> =
+    param!("part1-days", 80, "number of days simulated in part 1");
+static PART2_DAYS: Param<usize> =
+    param!("part2-days", 256, "number of days simulated in part 2");
 
 #[derive(StructOpt)]
 pub struct Day6 {
-    #[structopt(default_value = &INFILE_PATH)]
-    infile: PathBuf,
+    infile: Option<PathBuf>,
 }
 
 impl Day for Day6 {
+    fn params(&self) -> Vec<&'static dyn ParamSpec> {
+        vec![&PART1_DAYS, &PART2_DAYS]
+    }
+
+    fn generate(&self, rng: &mut InputRng, size: usize) -> GenerateResult {
+        let timers: Vec<_> = (0..300 * size)
+            .map(|_| rng.random_range(1..=5_usize).to_string())
+            .collect();
+        Ok(timers.join(",") + "\n")
+    }
+
     fn part1(&self) -> PartResult {
-        ANSWER!(self.calc_population(80)?)
+        ANSWER!(self.calc_population(1, PART1_DAYS.get()?)?)
     }
 
     fn part2(&self) -> PartResult {
-        ANSWER!(self.calc_population(256)?)
+        ANSWER!(self.calc_population(2, PART2_DAYS.get()?)?)
     }
 }
 
 impl Day6 {
-    fn calc_population(&self, days: usize) -> Result<usize, Box<dyn Error>> {
-        let mut input = String::new();
-        File::open(&self.infile)?.read_to_string(&mut input)?;
-
-        let maybe_timers: Result<Vec<usize>, _> =
-            input.trim().split(',').map(str::parse).collect();
-
-        let mut population = [0_usize; 9];
-        match maybe_timers {
-            Ok(timers) => {
-                for t in timers {
-                    population[t] += 1;
-                }
-            }
-            _ => {
-                return Err(
-                    format!("invalid initial population: {}", input).into()
-                )
-            }
-        }
-
-        for d in 0..days {
-            population[(d + 7) % 9] += population[d % 9];
-        }
-
-        Ok(population.iter().sum::<usize>())
+    fn calc_population(
+        &self,
+        part: u8,
+        days: usize,
+    ) -> Result<usize, Box<dyn Error>> {
+        let file = input::load(self.infile())?;
+        let text = file.text()?;
+        let mut school = School::try_from(Span::new(text))?;
+        let session = Session::start(module_path!(), part, &mut school)?;
+        session.run(&mut school, days)?;
+        school.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overflow_is_found_without_simulating_every_day() {
+        let day = Day6 { infile: Some(module_dir().join("test-input.txt")) };
+        // Once every count is saturated the school repeats itself, so the
+        // rest of the days are skipped.
+        let err = day.calc_population(2, usize::MAX).unwrap_err();
+        assert_eq!(err.to_string(), "the population overflows on day 512");
     }
 }
@@ -0,0 +1,97 @@
+use std::error::Error;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    days::snapshot::Snapshot,
+    parse::{ParseError, Span},
+    simulation::Simulation,
+};
+
+const N_TIMERS: usize = 9;
+
+/// The number of lanternfish with each timer value. The counts are stored
+/// rotated by the day so that a step only has to add one count.
+pub struct School {
+    population: [usize; N_TIMERS],
+    day: usize,
+    /// The first day on which a count no longer fit in a `usize`.
+    overflow: Option<usize>,
+}
+
+impl School {
+    pub fn len(&self) -> Result<usize, Box<dyn Error>> {
+        let len = match self.overflow {
+            Some(_) => None,
+            None => self
+                .population
+                .iter()
+                .try_fold(0_usize, |len, &n| len.checked_add(n)),
+        };
+        len.ok_or_else(|| {
+            let day = self.overflow.unwrap_or(self.day);
+            format!("the population overflows on day {}", day).into()
+        })
+    }
+}
+
+impl TryFrom<Span<'_>> for School {
+    type Error = ParseError;
+
+    fn try_from(timers: Span) -> Result<Self, Self::Error> {
+        let mut population = [0_usize; N_TIMERS];
+        for t in timers.trim().split(",") {
+            match t.trim().parse::<usize>()? {
+                timer if timer < N_TIMERS => population[timer] += 1,
+                timer => {
+                    return Err(
+                        t.error(format!("timer out of range: {}", timer))
+                    )
+                }
+            }
+        }
+        Ok(Self { population, day: 0, overflow: None })
+    }
+}
+
+impl Simulation for School {
+    type Output = ();
+    type State = [usize; N_TIMERS];
+
+    fn step(&mut self) {
+        let d = self.day;
+        let born = self.population[d % N_TIMERS];
+        let count = &mut self.population[(d + 7) % N_TIMERS];
+        *count = count.checked_add(born).unwrap_or_else(|| {
+            self.overflow.get_or_insert(d + 1);
+            usize::MAX
+        });
+        self.day += 1;
+    }
+
+    fn state(&self) -> Self::State {
+        let mut state = self.population;
+        state.rotate_left(self.day % N_TIMERS);
+        state
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SchoolState {
+    timers: [usize; N_TIMERS],
+}
+
+impl Snapshot for School {
+    type State = SchoolState;
+
+    fn save(&self) -> Self::State {
+        SchoolState { timers: self.state() }
+    }
+
+    fn restore(&mut self, state: Self::State) -> Result<(), Box<dyn Error>> {
+        self.population = state.timers;
+        self.day = 0;
+        self.overflow = None;
+        Ok(())
+    }
+}
@@ -1,18 +1,34 @@
-use std::{error::Error, fs::File, io::Read, path::PathBuf};
+use std::{error::Error, path::PathBuf};
 
+use rand::Rng;
 use structopt::StructOpt;
+use tracing::instrument;
 
-use super::{todays_input, Day, PartResult, ANSWER};
+use super::{
+    generate::InputRng, todays_input, Day, GenerateResult, PartResult, ANSWER,
+};
+use crate::{input, parse::Span};
 
-todays_input!(INFILE_PATH);
+todays_input!(Day7);
 
 #[derive(StructOpt)]
 pub struct Day7 {
-    #[structopt(default_value = &INFILE_PATH)]
-    infile: PathBuf,
+    infile: Option<PathBuf>,
 }
 
 impl Day for Day7 {
+    fn generate(&self, rng: &mut InputRng, size: usize) -> GenerateResult {
+        // Taking the lower of two positions crowds the crabs to the left,
+        // like in the real inputs.
+        let positions: Vec<_> = (0..1000 * size)
+            .map(|_| {
+                let a = rng.random_range(0..2000_usize);
+                a.min(rng.random_range(0..2000)).to_string()
+            })
+            .collect();
+        Ok(positions.join(",") + "\n")
+    }
+
     fn part1(&self) -> PartResult {
         let mut crabs = self.parse_crap_positions()?;
         crabs.sort();
@@ -21,7 +37,7 @@ impl Day for Day7 {
 
         let answer: usize = crabs
             .iter()
-            .map(|c| (*c as isize - median as isize).abs() as usize)
+            .map(|c| (*c as isize - median as isize).unsigned_abs())
             .sum();
 
         ANSWER!(answer)
@@ -35,7 +51,7 @@ impl Day for Day7 {
                 crabs
                     .iter()
                     .map(|c| {
-                        let dx = (*c as isize - $x as isize).abs() as usize;
+                        let dx = (*c as isize - $x as isize).unsigned_abs();
                         dx * (dx + 1) / 2 // (0..dx).sum()
                     })
                     .sum::<usize>()
@@ -71,14 +87,10 @@ impl Day for Day7 {
 }
 
 impl Day7 {
+    #[instrument(name = "parse", level = "debug", skip_all)]
     fn parse_crap_positions(&self) -> Result<Vec<usize>, Box<dyn Error>> {
-        let mut input = String::new();
-        File::open(&self.infile)?.read_to_string(&mut input)?;
-        input
-            .trim()
-            .split(',')
-            .map(str::parse)
-            .collect::<Result<_, _>>()
-            .map_err(|_| format!("invalid crab positions: {}", input).into())
+        let file = input::load(self.infile())?;
+        let text = file.text()?;
+        Ok(Span::new(text).trim().delimited(",")?)
     }
 }
@@ -1,28 +1,60 @@
-use std::{
-    error::Error,
-    fs::File,
-    io::{BufRead, BufReader},
-    path::PathBuf,
-};
+use std::{error::Error, fmt::Write, path::PathBuf};
 
+use rand::{seq::SliceRandom, Rng};
 use structopt::StructOpt;
+use tracing::instrument;
 
-use super::{todays_input, Day, PartResult, ANSWER};
+use super::{
+    explain::{self, Fact},
+    generate::InputRng,
+    todays_input, Day, GenerateResult, PartResult, ANSWER,
+};
+use crate::{input, parse};
 
 mod model;
 use self::model::Entry;
 
-mod util;
-
-todays_input!(INFILE_PATH);
+todays_input!(Day8);
 
 #[derive(StructOpt)]
 pub struct Day8 {
-    #[structopt(default_value = &INFILE_PATH)]
-    infile: PathBuf,
+    infile: Option<PathBuf>,
 }
 
 impl Day for Day8 {
+    fn generate(&self, rng: &mut InputRng, size: usize) -> GenerateResult {
+        const DIGITS: [&str; 10] = [
+            "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf",
+            "abcdefg", "abcdfg",
+        ];
+
+        let mut out = String::new();
+        for _ in 0..200 * size {
+            let mut wiring: Vec<char> = ('a'..='g').collect();
+            wiring.shuffle(rng);
+            let scramble = |digit: &str, rng: &mut InputRng| {
+                let mut segments: Vec<char> = digit
+                    .bytes()
+                    .map(|s| wiring[(s - b'a') as usize])
+                    .collect();
+                segments.shuffle(rng);
+                segments.into_iter().collect::<String>()
+            };
+
+            let mut patterns: Vec<_> =
+                DIGITS.iter().map(|d| scramble(d, rng)).collect();
+            let output: Vec<_> = (0..4)
+                .map(|_| {
+                    let digit = DIGITS[rng.random_range(0..10)];
+                    scramble(digit, rng)
+                })
+                .collect();
+            patterns.shuffle(rng);
+            writeln!(out, "{} | {}", patterns.join(" "), output.join(" "))?;
+        }
+        Ok(out)
+    }
+
     fn part1(&self) -> PartResult {
         let entries = self.parse_entries()?;
 
@@ -45,8 +77,14 @@ impl Day for Day8 {
         let entries = self.parse_entries()?;
 
         let mut answer = 0;
-        for entry in &entries {
-            answer += entry.solve();
+        for (i, entry) in entries.iter().enumerate() {
+            let value = entry.solve();
+            explain::fact(|| {
+                Fact::new(format!("entry {}", i + 1))
+                    .with("wiring", entry.wiring())
+                    .with("output", value)
+            });
+            answer += value;
         }
 
         ANSWER!(answer)
@@ -54,11 +92,12 @@ impl Day for Day8 {
 }
 
 impl Day8 {
+    #[instrument(name = "parse", level = "debug", skip_all)]
     fn parse_entries(&self) -> Result<Vec<Entry>, Box<dyn Error>> {
-        let file = BufReader::new(File::open(&self.infile)?);
-        let entries = file
-            .lines()
-            .map(|l| l.map_err(Into::into).and_then(|s| s.parse()))
+        let file = input::load(self.infile())?;
+        let text = file.text()?;
+        let entries = parse::lines(text)
+            .map(Entry::try_from)
             .collect::<Result<Vec<_>, _>>()?;
         Ok(entries)
     }
@@ -1,77 +1,120 @@
-use std::{collections::HashSet, error::Error, str::FromStr};
+use std::fmt::{self, Debug};
 
-use super::util::{set, ExpectIsolated};
+use crate::parse::{ParseError, Span};
 
-#[derive(Debug)]
-pub struct Entry {
-    pub digits: [HashSet<char>; 10],
-    pub output: [HashSet<char>; 4],
+/// The lit segments of a digit, one bit per segment `a` to `g`.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub struct Segments(u8);
+
+impl Segments {
+    #[inline]
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    #[inline]
+    fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    #[inline]
+    fn difference(self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+
+    /// The single segment left, e.g. after taking a difference.
+    fn expect_isolated(self) -> Self {
+        assert!(self.len() == 1, "failed to isolate segment: {:?}", self);
+        self
+    }
 }
 
-impl FromStr for Entry {
-    type Err = Box<dyn Error>;
+impl TryFrom<Span<'_>> for Segments {
+    type Error = ParseError;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut components = s.split(" | ");
+    fn try_from(span: Span) -> Result<Self, Self::Error> {
+        span.text().bytes().try_fold(Self(0), |segments, b| match b {
+            b'a'..=b'g' => Ok(Self(segments.0 | 1 << (b - b'a'))),
+            _ => Err(span.error(format!("invalid segment: {}", b as char))),
+        })
+    }
+}
 
-        macro_rules! parse_digits {
-            ($d_str:expr) => {
-                $d_str
-                    .trim()
-                    .split_whitespace()
-                    .map(|s| s.chars().collect::<HashSet<_>>())
-                    .collect::<Vec<_>>()
-            };
+impl Debug for Segments {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, c) in ('a'..='g').enumerate() {
+            if self.0 & 1 << i != 0 {
+                write!(f, "{}", c)?;
+            }
         }
+        Ok(())
+    }
+}
 
-        let digits = match components.next() {
-            Some(d) => parse_digits!(d).try_into().map_err(|_| {
-                format!("invalid number of digits in entry: {}", s).to_string()
-            })?,
-            None => return Err(format!("no digits in entry: {}", s).into()),
-        };
+#[derive(Debug)]
+pub struct Entry {
+    pub digits: [Segments; 10],
+    pub output: [Segments; 4],
+}
 
-        let output = match components.next() {
-            Some(d) => parse_digits!(d).try_into().map_err(|_| {
-                format!("invalid number of output digits in entry: {}", s)
-                    .to_string()
-            })?,
-            None => return Err(format!("no output in entry: {}", s).into()),
-        };
+impl TryFrom<Span<'_>> for Entry {
+    type Error = ParseError;
 
-        if components.next().is_some() {
-            return Err(format!("invalid entry: {}", s).into());
-        }
+    fn try_from(line: Span) -> Result<Self, Self::Error> {
+        let (digits, output) = line.split_once(" | ")?;
+        Ok(Self {
+            digits: parse_digits(digits, "digits")?,
+            output: parse_digits(output, "output digits")?,
+        })
+    }
+}
 
-        Ok(Self { digits, output })
+fn parse_digits<const N: usize>(
+    span: Span,
+    what: &str,
+) -> Result<[Segments; N], ParseError> {
+    let mut digits = [Segments::default(); N];
+    let mut n = 0;
+    for d in span.split_whitespace() {
+        if n == N {
+            return Err(d.error(format!("too many {}", what)));
+        }
+        digits[n] = Segments::try_from(d)?;
+        n += 1;
+    }
+    if n < N {
+        return Err(span.error(format!("expected {} {}, found {}", N, what, n)));
     }
+    Ok(digits)
 }
 
 impl Entry {
-    pub fn solve(&self) -> usize {
-        let digit1 = self
+    /// The wires lighting segments `c`, `e` and `f`, which tell the digits
+    /// with the same number of segments apart.
+    fn deduce(&self) -> (Segments, Segments, Segments) {
+        let digit1 = *self
             .digits
             .iter()
             .find(|d| d.len() == 2)
             .expect("failed to find digit 1");
 
         let mut maybe_c = None;
-        for digit in self.digits.iter().filter(|n| n.len() == 6) {
-            let diff = digit1.difference(digit).collect::<Vec<_>>();
+        for &digit in self.digits.iter().filter(|n| n.len() == 6) {
+            let diff = digit1.difference(digit);
             if diff.len() == 1 {
-                maybe_c = Some(**diff.iter().expect_isolated());
+                maybe_c = Some(diff);
                 break;
             }
         }
         let c = maybe_c.expect("failed to isolate c");
 
-        let f = *digit1.difference(&set![c]).expect_isolated();
+        let f = digit1.difference(c).expect_isolated();
 
         let mut maybe_digit2 = None;
         let mut maybe_digit3 = None;
-        for d in self.digits.iter().filter(|d| d.len() == 5) {
-            if d.contains(&c) {
-                if d.contains(&f) {
+        for &d in self.digits.iter().filter(|d| d.len() == 5) {
+            if d.contains(c) {
+                if d.contains(f) {
                     maybe_digit3 = Some(d);
                 } else {
                     maybe_digit2 = Some(d);
@@ -81,10 +124,41 @@ impl Entry {
         let digit2 = maybe_digit2.expect("failed to isolate digit 2");
         let digit3 = maybe_digit3.expect("failed to isolate digit 3");
 
-        let e = *digit2.difference(&digit3).expect_isolated();
+        let e = digit2.difference(digit3).expect_isolated();
+
+        (c, e, f)
+    }
+
+    /// The wire lighting each segment, as `a→x b→y …`.
+    pub fn wiring(&self) -> String {
+        let (c, e, f) = self.deduce();
+        let find = |len: usize| {
+            *self
+                .digits
+                .iter()
+                .find(|d| d.len() == len && d.contains(c) && d.contains(f))
+                .expect("failed to find digit")
+        };
+        let (digit1, digit7) = (find(2), find(3));
+        let (digit4, digit3) = (find(4), find(5));
+
+        let a = digit7.difference(digit1);
+        let b = digit4.difference(digit1).difference(digit3);
+        let d = digit4.difference(digit1).difference(b);
+        let g = digit3.difference(digit7).difference(d);
+
+        ('a'..='g')
+            .zip([a, b, c, d, e, f, g])
+            .map(|(segment, wire)| format!("{}→{:?}", segment, wire))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    pub fn solve(&self) -> usize {
+        let (c, e, f) = self.deduce();
 
         let mut answer = 0;
-        for output in &self.output {
+        for &output in &self.output {
             answer *= 10;
             answer += match output.len() {
                 2 => 1,
@@ -96,8 +170,8 @@ impl Entry {
                 //   3 => c, f
                 //   5 => b, f
                 5 =>
-                    if output.contains(&c) {
-                        if output.contains(&f) {
+                    if output.contains(c) {
+                        if output.contains(f) {
                             3
                         } else {
                             2
@@ -110,8 +184,8 @@ impl Entry {
                 //   6 => d, e
                 //   9 => c, d
                 6 =>
-                    if output.contains(&c) {
-                        if output.contains(&e) {
+                    if output.contains(c) {
+                        if output.contains(e) {
                             0
                         } else {
                             9
@@ -1,96 +1,61 @@
 use std::{
-    collections::VecDeque,
-    error::Error,
-    fs::File,
-    io::{BufRead, BufReader},
-    path::PathBuf,
+    collections::VecDeque, convert::Infallible, error::Error, path::PathBuf,
 };
 
+use rand::Rng;
 use structopt::StructOpt;
+use tracing::instrument;
 
-use super::{todays_input, Day, PartResult, ANSWER};
+use super::{
+    explain::{self, Fact},
+    generate::InputRng,
+    todays_input, Day, GenerateResult, PartResult, ANSWER,
+};
+use crate::{
+    animation::{Frame, Recorder},
+    grid::Grid,
+    input,
+};
 
 mod model;
-use self::model::Height;
+use self::model::{Height, BASIN_PALETTE};
 
-todays_input!(INFILE_PATH);
+todays_input!(Day9);
 
 #[derive(StructOpt)]
 pub struct Day9 {
-    #[structopt(default_value = &INFILE_PATH)]
-    infile: PathBuf,
+    infile: Option<PathBuf>,
 }
 
 impl Day for Day9 {
-    fn part1(&self) -> PartResult {
-        let heights = self.parse_height_map()?;
-        let max_i = heights.len() - 1;
-        let max_j = heights[0].len() - 1;
-
-        let mut local_min_sums = 0;
-
-        // Check first row
-        if heights[0][0] < heights[0][1] && heights[0][0] < heights[1][0] {
-            local_min_sums += heights[0][0] + 1;
-        }
-        for j in 1..max_j {
-            if heights[0][j] < heights[0][j - 1]
-                && heights[0][j] < heights[0][j + 1]
-                && heights[0][j] < heights[1][j]
-            {
-                local_min_sums += heights[0][j] + 1;
+    fn generate(&self, rng: &mut InputRng, size: usize) -> GenerateResult {
+        let side = 100 * size;
+        let mut out = String::with_capacity(side * (side + 1));
+        for _ in 0..side {
+            for _ in 0..side {
+                let height = if rng.random_bool(0.3) {
+                    9
+                } else {
+                    rng.random_range(0..9)
+                };
+                out.push(char::from(b'0' + height));
             }
+            out.push('\n');
         }
-        if heights[0][max_j] < heights[0][max_j - 1]
-            && heights[0][max_j] < heights[1][max_j]
-        {
-            local_min_sums += heights[0][max_j] + 1;
-        }
+        Ok(out)
+    }
 
-        // Check middle rows
-        for i in 1..max_i {
-            if heights[i][0] < heights[i][1]
-                && heights[i][0] < heights[i - 1][0]
-                && heights[i][0] < heights[i + 1][0]
-            {
-                local_min_sums += heights[i][0] + 1;
-            }
-            for j in 1..max_j {
-                if heights[i][j] < heights[i][j - 1]
-                    && heights[i][j] < heights[i][j + 1]
-                    && heights[i][j] < heights[i - 1][j]
-                    && heights[i][j] < heights[i + 1][j]
-                {
-                    local_min_sums += heights[i][j] + 1;
-                }
-            }
-            if heights[i][max_j] < heights[i][max_j - 1]
-                && heights[i][max_j] < heights[i - 1][max_j]
-                && heights[i][max_j] < heights[i + 1][max_j]
-            {
-                local_min_sums += heights[i][max_j] + 1;
-            }
-        }
+    fn part1(&self) -> PartResult {
+        let file = input::load(self.infile())?;
+        let heights: Grid<usize> = Grid::parse_digits(file.text()?)?;
 
-        // Check last row
-        if heights[max_i][0] < heights[max_i][1]
-            && heights[max_i][0] < heights[max_i - 1][0]
-        {
-            local_min_sums += heights[max_i][0] + 1;
-        }
-        for j in 1..max_j {
-            if heights[max_i][j] < heights[max_i][j - 1]
-                && heights[max_i][j] < heights[max_i][j + 1]
-                && heights[max_i][j] < heights[max_i - 1][j]
-            {
-                local_min_sums += heights[max_i][j] + 1;
-            }
-        }
-        if heights[max_i][max_j] < heights[max_i][max_j - 1]
-            && heights[max_i][max_j] < heights[max_i - 1][max_j]
-        {
-            local_min_sums += heights[max_i][max_j] + 1;
-        }
+        let local_min_sums: usize = heights
+            .positions()
+            .filter(|&pos| {
+                heights.neighbors4(pos).all(|n| heights[pos] < heights[n])
+            })
+            .map(|pos| heights[pos] + 1)
+            .sum();
 
         ANSWER!(local_min_sums)
     }
@@ -98,12 +63,17 @@ impl Day for Day9 {
     fn part2(&self) -> PartResult {
         let mut heights = self.parse_basin_map()?;
 
+        let mut recorder = Recorder::new("Day 9: Smoke Basin", BASIN_PALETTE);
         let mut n_basins = 0;
-        for y in 0..heights.len() {
-            for x in 0..heights[0].len() {
-                if fill_basin(&mut heights, x, y, n_basins) {
-                    n_basins += 1;
-                }
+        for pos in heights.positions() {
+            if fill_basin(&mut heights, pos, n_basins) {
+                n_basins += 1;
+                recorder.frame(|| {
+                    Frame::new(
+                        heights.map(Height::frame_value),
+                        format!("{} basins", n_basins),
+                    )
+                });
             }
         }
 
@@ -112,72 +82,53 @@ impl Day for Day9 {
         }
 
         let mut basin_sizes = vec![0_usize; n_basins];
-        for h in heights.iter().flatten() {
+        for h in heights.iter() {
             if let Height::Basin(Some(i)) = h {
                 basin_sizes[*i] += 1;
             }
         }
 
         basin_sizes.sort();
-        let answer = basin_sizes.iter().rev().take(3).fold(1, |acc, s| acc * s);
+        explain::fact(|| {
+            let sizes: Vec<_> =
+                basin_sizes.iter().rev().map(usize::to_string).collect();
+            Fact::new("basins")
+                .with("count", n_basins)
+                .with("sizes", sizes.join(" "))
+        });
+        let answer = basin_sizes.iter().rev().take(3).product::<usize>();
         ANSWER!(answer)
     }
 }
 
 impl Day9 {
-    fn parse_height_map(&self) -> Result<Vec<Vec<usize>>, Box<dyn Error>> {
-        let file = BufReader::new(File::open(&self.infile)?);
-
-        let mut heights = Vec::new();
-
-        for line_res in file.lines() {
-            let line = line_res
-                .map_err(|e| format!("invalid line: {}", e).to_string())?;
-            heights.push(
-                line.bytes()
-                    .map(|b| b as usize - '0' as usize)
-                    .collect::<Vec<_>>(),
-            );
-        }
-
-        Ok(heights)
-    }
-
-    fn parse_basin_map(&self) -> Result<Vec<Vec<Height>>, Box<dyn Error>> {
-        let file = BufReader::new(File::open(&self.infile)?);
-
-        let mut heights = Vec::new();
-
-        for line_res in file.lines() {
-            let line = line_res
-                .map_err(|e| format!("invalid line: {}", e).to_string())?;
-            heights.push(line.chars().map(Into::into).collect());
-        }
-
-        Ok(heights)
+    #[instrument(name = "parse", level = "debug", skip_all)]
+    fn parse_basin_map(&self) -> Result<Grid<Height>, Box<dyn Error>> {
+        let file = input::load(self.infile())?;
+        let text = file.text()?;
+        Ok(Grid::parse_chars(text, |c| Ok::<_, Infallible>(Height::from(c)))?)
     }
 }
 
 fn fill_basin(
-    heights: &mut Vec<Vec<Height>>,
-    x: usize,
-    y: usize,
+    heights: &mut Grid<Height>,
+    (x, y): (usize, usize),
     basin_i: usize,
 ) -> bool {
-    match heights[y][x] {
+    match heights[(x, y)] {
         Height::Basin(None) => (),
         _ => return false,
     }
 
-    let max_y = heights.len() - 1;
-    let max_x = heights[0].len() - 1;
+    let max_y = heights.height() - 1;
+    let max_x = heights.width() - 1;
     let mut queue = VecDeque::from([(x, y)]);
 
     macro_rules! scan {
         ($lx:expr, $rx:expr, $y:expr) => {{
             let mut added = false;
             for x in $lx..$rx {
-                if heights[$y][x] != Height::Basin(None) {
+                if heights[(x, $y)] != Height::Basin(None) {
                     added = false;
                 } else if !added {
                     queue.push_back((x, $y));
@@ -189,12 +140,12 @@ fn fill_basin(
 
     while let Some((mut x, y)) = queue.pop_front() {
         let mut lx = x;
-        while lx > 0 && heights[y][lx - 1] == Height::Basin(None) {
+        while lx > 0 && heights[(lx - 1, y)] == Height::Basin(None) {
             lx -= 1;
-            heights[y][lx] = Height::Basin(Some(basin_i));
+            heights[(lx, y)] = Height::Basin(Some(basin_i));
         }
-        while x <= max_x && heights[y][x] == Height::Basin(None) {
-            heights[y][x] = Height::Basin(Some(basin_i));
+        while x <= max_x && heights[(x, y)] == Height::Basin(None) {
+            heights[(x, y)] = Height::Basin(Some(basin_i));
             x += 1;
         }
         if x > 0 {
@@ -1,3 +1,18 @@
+use crate::animation::{Palette, Swatch};
+
+/// Borders, unfilled basin cells and then the colours filled basins cycle
+/// through.
+pub const BASIN_PALETTE: Palette = &[
+    Swatch::new('9', (90, 90, 90)),
+    Swatch::new('.', (40, 40, 40)),
+    Swatch::new('#', (230, 80, 80)),
+    Swatch::new('%', (240, 180, 60)),
+    Swatch::new('@', (120, 210, 90)),
+    Swatch::new('&', (70, 190, 220)),
+    Swatch::new('*', (110, 110, 240)),
+    Swatch::new('+', (210, 100, 220)),
+];
+
 #[derive(PartialEq, Debug)]
 #[repr(u8)]
 pub enum Height {
@@ -5,6 +20,17 @@ pub enum Height {
     Border,
 }
 
+impl Height {
+    /// The value of this cell in a frame drawn with [`BASIN_PALETTE`].
+    pub fn frame_value(&self) -> u8 {
+        match self {
+            Self::Border => 0,
+            Self::Basin(None) => 1,
+            Self::Basin(Some(i)) => 2 + (i % (BASIN_PALETTE.len() - 2)) as u8,
+        }
+    }
+}
+
 impl From<char> for Height {
     fn from(c: char) -> Self {
         if c == '9' {
@@ -0,0 +1,61 @@
+use std::{
+    fmt::{self, Display},
+    mem,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
+
+use crate::style::THEME;
+
+static EXPLAINING: AtomicBool = AtomicBool::new(false);
+static FACTS: Mutex<Vec<Fact>> = Mutex::new(Vec::new());
+
+/// Something a day worked out on the way to its answer, such as the
+/// wiring of a day 8 display, as named values about a subject.
+pub struct Fact {
+    pub subject: String,
+    pub values: Vec<(&'static str, String)>,
+}
+
+#[cfg(feature = "explain")]
+impl Fact {
+    pub fn new(subject: impl Into<String>) -> Self {
+        Self { subject: subject.into(), values: Vec::new() }
+    }
+
+    pub fn with(mut self, name: &'static str, value: impl Display) -> Self {
+        self.values.push((name, value.to_string()));
+        self
+    }
+}
+
+impl Display for Fact {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:", THEME.heading.paint(&self.subject))?;
+        for (name, value) in &self.values {
+            write!(f, " {}={}", THEME.muted.paint(name), value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Add a fact. `make_fact` is only called once explaining was turned on
+/// with [`start`].
+#[cfg(feature = "explain")]
+#[inline]
+pub fn fact(make_fact: impl FnOnce() -> Fact) {
+    if EXPLAINING.load(Ordering::Relaxed) {
+        FACTS.lock().unwrap().push(make_fact());
+    }
+}
+
+pub fn start() {
+    EXPLAINING.store(true, Ordering::Relaxed);
+}
+
+/// The facts added since the last call.
+pub fn take() -> Vec<Fact> {
+    mem::take(&mut *FACTS.lock().unwrap())
+}
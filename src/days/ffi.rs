@@ -0,0 +1,110 @@
+use std::{
+    error::Error,
+    ffi::{c_char, CString},
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    ptr, slice,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use super::{CliDay, TimedSolution};
+use crate::input;
+
+/// Tells apart the inputs of calls made at the same time.
+static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+/// The outcome of solving a part. Exactly one of `answer` and `error` is
+/// set. Release it with `aoc_result_free`.
+#[repr(C)]
+pub struct AocResult {
+    /// The answer as the puzzle website takes it, such as the letters a
+    /// day 13 picture reads as.
+    pub answer: *mut c_char,
+    pub error: *mut c_char,
+    /// Microseconds the part took, including reading the input.
+    pub time_us: f64,
+}
+
+/// Whether `day` has a solver.
+#[no_mangle]
+pub extern "C" fn aoc_has_day(day: u32) -> bool {
+    CliDay::day_numbers().contains(&day)
+}
+
+/// Solve `part` (1 or 2) of `day` for the `len` bytes of UTF-8 text at
+/// `input`, which the call does not keep.
+///
+/// # Safety
+///
+/// `input` must point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    day: u32,
+    part: u32,
+    input: *const u8,
+    len: usize,
+) -> AocResult {
+    let input =
+        if len == 0 { &[][..] } else { slice::from_raw_parts(input, len) };
+    let solved =
+        panic::catch_unwind(AssertUnwindSafe(|| solve(day, part, input)));
+
+    let (answer, error, time) = match solved {
+        Ok(Ok(timed)) => match timed.solution {
+            Ok(answer) => {
+                let text =
+                    answer.submission().unwrap_or_else(|_| answer.to_string());
+                (Some(text), None, timed.time)
+            }
+            Err(e) => (None, Some(e.to_string()), timed.time),
+        },
+        Ok(Err(e)) => (None, Some(e.to_string()), 0.0),
+        Err(_) => (None, Some("the solver panicked".to_string()), 0.0),
+    };
+    AocResult {
+        answer: answer.map_or(ptr::null_mut(), into_c_string),
+        error: error.map_or(ptr::null_mut(), into_c_string),
+        time_us: time as f64,
+    }
+}
+
+/// Release the strings of a result returned by `aoc_solve`.
+///
+/// # Safety
+///
+/// `result` must come from `aoc_solve` and not have been released yet.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_result_free(result: AocResult) {
+    for text in [result.answer, result.error] {
+        if !text.is_null() {
+            drop(CString::from_raw(text));
+        }
+    }
+}
+
+/// Days load their input by path, so the buffer is held in memory under a
+/// path of its own while the part runs.
+fn solve(
+    day: u32,
+    part: u32,
+    data: &[u8],
+) -> Result<TimedSolution, Box<dyn Error>> {
+    let calls = CALLS.fetch_add(1, Ordering::Relaxed);
+    let path = PathBuf::from(format!("<input of call {}>", calls));
+    let solver = CliDay::day_with_input(day, &path)
+        .ok_or_else(|| format!("no such day: {}", day))?;
+    if part != 1 && part != 2 {
+        return Err(format!("no such part: {}", part).into());
+    }
+
+    let _input = input::hold(path, data.to_vec());
+    match part {
+        1 => solver.run_part1(),
+        _ => solver.run_part2(),
+    }
+}
+
+/// Error messages may contain NUL bytes, which C strings cannot.
+fn into_c_string(text: String) -> *mut c_char {
+    CString::new(text.replace('\0', "\u{fffd}")).unwrap_or_default().into_raw()
+}
@@ -0,0 +1,10 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+/// The random number generator handed to the days' input generators. It is
+/// a fixed algorithm, so a seed always produces the same input.
+pub type InputRng = ChaCha8Rng;
+
+pub fn rng(seed: u64) -> InputRng {
+    InputRng::seed_from_u64(seed)
+}
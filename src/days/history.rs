@@ -0,0 +1,79 @@
+use std::{
+    error::Error,
+    fs::{self, OpenOptions},
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::Answer;
+use crate::config;
+
+const HISTORY_FILE: &str = "bench-history.toml";
+
+/// The results of one `--bench` invocation.
+#[derive(Serialize, Deserialize)]
+pub struct Run {
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    pub days: Vec<Record>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Record {
+    pub day: u32,
+    /// Average milliseconds of each part.
+    pub times: [f64; 2],
+    /// The answers, missing for parts that failed.
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+/// The file is a list of `[[run]]` tables, so runs are saved by appending.
+#[derive(Default, Serialize, Deserialize)]
+struct History {
+    #[serde(default)]
+    run: Vec<Run>,
+}
+
+/// The history file from the config, or `bench-history.toml` in the crate
+/// root.
+fn path() -> PathBuf {
+    config::get().history.clone().unwrap_or_else(|| {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join(HISTORY_FILE)
+    })
+}
+
+impl Run {
+    pub fn now(days: Vec<Record>) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        Self { timestamp, days }
+    }
+
+    pub fn save(self) -> Result<(), Box<dyn Error>> {
+        let path = path();
+        let toml = toml::to_string(&History { run: vec![self] })?;
+        let mut file =
+            OpenOptions::new().create(true).append(true).open(&path)?;
+        writeln!(file, "{}", toml)
+            .map_err(|e| format!("cannot save to {}: {}", path.display(), e))?;
+        Ok(())
+    }
+}
+
+/// The saved runs, oldest first.
+pub fn load() -> Result<Vec<Run>, Box<dyn Error>> {
+    let path = path();
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let history: History = toml::from_str(&text)
+        .map_err(|e| format!("invalid {}: {}", path.display(), e))?;
+    Ok(history.run)
+}
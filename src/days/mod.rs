@@ -1,15 +1,75 @@
-use std::{error::Error, fmt::Display, time::SystemTime};
+use std::{
+    any::type_name,
+    error::Error,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
 
 use structopt::StructOpt;
-use term_size::dimensions_stdout;
+use tracing::{info, info_span};
 
-use crate::util::repeat_char;
+use crate::{
+    animation,
+    config::{self, Config},
+    input,
+    style::{self, ColorChoice, THEME},
+    trace,
+    util::repeat_char,
+};
 
-pub type PartResult = Result<Box<dyn Display>, Box<dyn Error>>;
+mod answer;
+pub use self::answer::Answer;
+#[cfg(feature = "letters")]
+pub use self::answer::{draw_letters, drawable_letters};
+
+mod budget;
+
+mod cache;
+use self::cache::Cache;
+
+#[cfg(feature = "tui")]
+mod dashboard;
+
+mod explain;
+use self::explain::Fact;
+
+#[cfg(feature = "ffi")]
+mod ffi;
+
+mod history;
+
+pub mod generate;
+use self::generate::InputRng;
+
+pub mod params;
+use self::params::{ParamArg, ParamSpec};
+
+#[cfg(feature = "plugins")]
+mod plugin;
+#[cfg(feature = "plugins")]
+use self::plugin::PluginDay;
+
+mod report;
+
+mod scale;
+
+#[cfg(feature = "serve")]
+mod serve;
+
+mod snapshot;
+
+pub type PartResult = Result<Answer, Box<dyn Error>>;
+
+pub type GenerateResult = Result<String, Box<dyn Error>>;
 
 pub struct TimedSolution {
     solution: PartResult,
     time: f32,
+    /// What the part explained with `--explain`.
+    facts: Vec<Fact>,
+    /// Whether the answer came from the answer cache, with the time it
+    /// took when it was solved.
+    cached: bool,
 }
 
 impl TimedSolution {
@@ -20,24 +80,54 @@ impl TimedSolution {
         let solution = solver();
         let end = SystemTime::now();
         let time = end.duration_since(begin)?.as_nanos() as f32 / 1.0e3;
-        Ok(Self { solution, time })
+        match &solution {
+            Ok(answer) => info!(%answer, time_us = time, "solved"),
+            Err(e) => info!(error = %e, time_us = time, "failed"),
+        }
+        Ok(Self { solution, time, facts: explain::take(), cached: false })
+    }
+
+    fn cached(answer: Answer, time: f32) -> Self {
+        Self { solution: Ok(answer), time, facts: Vec::new(), cached: true }
     }
 
     fn print(&self) {
+        for fact in &self.facts {
+            println!("{}", fact);
+        }
         match &self.solution {
-            Ok(answer) => println!("{}", answer),
-            Err(err) => println!("{:?}", err),
+            Ok(answer) => {
+                println!("{}", THEME.answer.paint(answer));
+                if let (Answer::Grid(_), Ok(letters)) =
+                    (answer, answer.submission())
+                {
+                    println!(
+                        "{}",
+                        THEME.muted.paint(format!("[reads as {}]", letters))
+                    );
+                }
+            }
+            Err(err) => println!("{}", THEME.error.paint(format!("{:?}", err))),
         }
-        println!("[answer in {} μs]", self.time);
+        let time = if self.cached {
+            format!("[cached, answer took {} μs]", self.time)
+        } else {
+            format!("[answer in {} μs]", self.time)
+        };
+        println!("{}", THEME.muted.paint(time));
     }
 }
 
 pub trait Day {
     fn run_part1(&self) -> Result<TimedSolution, Box<dyn Error>> {
+        let _span =
+            info_span!("part", day = day_name::<Self>(), part = 1).entered();
         TimedSolution::calculate(|| self.part1())
     }
 
     fn run_part2(&self) -> Result<TimedSolution, Box<dyn Error>> {
+        let _span =
+            info_span!("part", day = day_name::<Self>(), part = 2).entered();
         TimedSolution::calculate(|| self.part2())
     }
 
@@ -47,73 +137,548 @@ pub trait Day {
 
     fn run_and_print(&self) -> Result<(f32, f32), Box<dyn Error>> {
         let (ts1, ts2) = self.run()?;
-        println!("\n=== Part 1 ===");
-        ts1.print();
-        println!("\n=== Part 2 ===");
-        ts2.print();
-        println!("");
+        let _span = info_span!("print", day = day_name::<Self>()).entered();
+        print_parts(&ts1, &ts2);
         Ok((ts1.time, ts2.time))
     }
 
+    fn params(&self) -> Vec<&'static dyn ParamSpec> {
+        Vec::new()
+    }
+
+    /// A random input, with `size` 1 being about as large as a real one.
+    fn generate(&self, _rng: &mut InputRng, _size: usize) -> GenerateResult {
+        Err("this day has no input generator".into())
+    }
+
     fn part1(&self) -> PartResult;
 
     fn part2(&self) -> PartResult;
 }
 
+fn print_parts(ts1: &TimedSolution, ts2: &TimedSolution) {
+    println!("\n{}", THEME.heading.paint("=== Part 1 ==="));
+    ts1.print();
+    println!("\n{}", THEME.heading.paint("=== Part 2 ==="));
+    ts2.print();
+    println!();
+}
+
+/// The name of a day's type, such as `Day11`, to tell spans apart.
+fn day_name<D: ?Sized>() -> &'static str {
+    type_name::<D>().rsplit("::").next().unwrap_or_default()
+}
+
 #[derive(StructOpt)]
 pub struct Cli {
     #[structopt(long)]
     bench: bool,
 
+    /// Directory to look for `dayNN/input.txt` or `NN.txt` inputs in
+    #[structopt(long, env = "AOC_INPUTS_DIR", parse(from_os_str))]
+    inputs_dir: Option<PathBuf>,
+
+    /// Directory to load plugin days from
+    #[cfg(feature = "plugins")]
+    #[structopt(long, env = "AOC_PLUGINS_DIR", parse(from_os_str))]
+    plugins_dir: Option<PathBuf>,
+
+    /// Override a puzzle parameter, as `name=value` or `dayN.name=value`
+    #[structopt(long = "param", number_of_values = 1)]
+    params: Vec<ParamArg>,
+
+    /// Stop the simulations of the days that support it after this many
+    /// steps, dumping their state when `--dump` is given
+    #[structopt(long)]
+    stop_at: Option<usize>,
+
+    /// Write the state of the simulations to a JSON snapshot file, when
+    /// they stop or else when they are done
+    #[structopt(long, parse(from_os_str))]
+    dump: Option<PathBuf>,
+
+    /// Resume the simulations from a snapshot file written by `--dump`
+    #[structopt(long, parse(from_os_str))]
+    resume: Option<PathBuf>,
+
+    /// Print the intermediate facts the days that support it work out
+    #[structopt(long)]
+    explain: bool,
+
+    /// Solve every part in `all` instead of using the answers cached for
+    /// the same input and build
+    #[structopt(long)]
+    no_cache: bool,
+
+    /// Animate the days that support it after solving them
+    #[structopt(long)]
+    visualize: bool,
+
+    /// Save the animations of the days that support them as a .gif or .png
+    #[cfg(feature = "export")]
+    #[structopt(long, parse(from_os_str))]
+    export_animation: Option<PathBuf>,
+
+    /// Frames per second of the animations
+    #[structopt(long, default_value = "10")]
+    fps: f32,
+
+    /// When to color the output: auto, always or never
+    #[structopt(long, default_value = "auto")]
+    color: ColorChoice,
+
+    /// Log more, once for info, twice for debug and three times for trace;
+    /// `RUST_LOG` takes precedence
+    #[structopt(short, long, parse(from_occurrences))]
+    verbose: u8,
+
+    /// Write the spans of the run to a Chrome trace JSON file
+    #[structopt(long, parse(from_os_str))]
+    chrome_trace: Option<PathBuf>,
+
     #[structopt(subcommand)]
     day: CliDay,
 }
 
+#[derive(StructOpt)]
+pub struct CliGenerate {
+    /// The day to generate an input for
+    day: u32,
+
+    /// Seed of the random generator; the same seed gives the same input
+    #[structopt(long, default_value = "0")]
+    seed: u64,
+
+    /// Scale of the input, where 1 is about the size of a real input
+    #[structopt(long, default_value = "1")]
+    size: usize,
+}
+
+#[derive(StructOpt)]
+pub struct CliScale {
+    /// The day to time
+    day: u32,
+
+    /// Seed of the random generator
+    #[structopt(long, default_value = "0")]
+    seed: u64,
+
+    /// Largest input scale to time; scales double from 1 up to it
+    #[structopt(long, default_value = "16")]
+    max_size: usize,
+
+    /// Stop growing the input once a part takes more seconds than this
+    #[structopt(long, default_value = "5")]
+    max_time: f64,
+
+    /// Also write the timings to a CSV file
+    #[structopt(long, parse(from_os_str))]
+    export: Option<PathBuf>,
+}
+
+#[derive(StructOpt)]
+pub struct CliBudget {
+    /// Milliseconds for all the days together, instead of the `[budget]`
+    /// config or one second
+    #[structopt(long)]
+    total: Option<f64>,
+}
+
+#[derive(StructOpt)]
+pub struct CliReport {
+    /// The HTML file to write
+    #[structopt(long, default_value = "report.html", parse(from_os_str))]
+    output: PathBuf,
+
+    /// Take the answers and timings of each day from its latest saved bench
+    /// run instead of running the days
+    #[structopt(long)]
+    saved: bool,
+}
+
+#[cfg(feature = "serve")]
+#[derive(StructOpt)]
+pub struct CliServe {
+    /// The port to listen on at 127.0.0.1
+    #[structopt(long, default_value = "8080")]
+    port: u16,
+}
+
+#[cfg(feature = "plugins")]
+#[derive(StructOpt)]
+pub struct CliPlugin {
+    /// The name the plugin gives the day, as shown by `list`
+    name: String,
+
+    infile: Option<PathBuf>,
+}
+
+#[derive(StructOpt)]
+pub struct CliCryptInputs {
+    /// Delete the source files afterwards
+    #[structopt(long)]
+    remove: bool,
+}
+
+#[derive(StructOpt)]
+pub enum CliCache {
+    /// Delete the cached answers
+    Clear,
+}
+
 impl Cli {
     pub fn run(&self) -> Result<(), Box<dyn Error>> {
+        style::init(self.color);
+        let _trace = trace::init(self.verbose, self.chrome_trace.as_deref())?;
+
+        let mut config = Config::load()?;
+        if let Some(dir) = &self.inputs_dir {
+            config.inputs_dir = Some(dir.clone());
+        }
+        #[cfg(feature = "plugins")]
+        if let Some(dir) = &self.plugins_dir {
+            config.plugins_dir = Some(dir.clone());
+        }
+        config::init(config);
+
+        params::init(self.params.clone(), &self.day.params())?;
+        snapshot::init(snapshot::Options {
+            stop_at: self.stop_at,
+            dump: self.dump.clone(),
+            resume: self.resume.clone(),
+        })?;
+
+        if self.explain {
+            explain::start();
+        }
+
         if self.bench {
             self.day.bench()
         } else {
-            self.day.run()
+            if self.visualize || self.exports_animation() {
+                animation::start_recording();
+            }
+            self.day.run(self.fps, self.use_cache())?;
+
+            let animations = animation::take_recorded();
+            if self.visualize {
+                animation::play(&animations, self.fps)?;
+            }
+            #[cfg(feature = "export")]
+            if let Some(path) = &self.export_animation {
+                animation::export(&animations, path, self.fps)?;
+            }
+            Ok(())
         }
     }
+
+    #[cfg(feature = "export")]
+    fn exports_animation(&self) -> bool {
+        self.export_animation.is_some()
+    }
+
+    #[cfg(not(feature = "export"))]
+    fn exports_animation(&self) -> bool {
+        false
+    }
+
+    /// Cached answers come without explanations, animations or snapshots,
+    /// so the cache is only used when none of those were asked for.
+    fn use_cache(&self) -> bool {
+        !self.no_cache
+            && !self.explain
+            && !self.visualize
+            && !self.exports_animation()
+            && self.stop_at.is_none()
+            && self.dump.is_none()
+            && self.resume.is_none()
+    }
 }
 
 macro_rules! decl_day {
-    ($($mod:ident => $cli:ident;)+) => {
+    ($($(#[$attr:meta])* $mod:ident => $cli:ident;)+) => {
         $(
+            $(#[$attr])*
             mod $mod;
+            $(#[$attr])*
             use $mod::$cli;
         )+
 
         #[derive(StructOpt)]
         pub enum CliDay {
-            $($cli($cli),)+
+            $($(#[$attr])* $cli($cli),)+
             All,
+            /// List the days and their parameters
+            List,
+            /// Encrypt each day's input into an `.enc` file
+            EncryptInputs(CliCryptInputs),
+            /// Decrypt each day's `.enc` file into its input
+            DecryptInputs(CliCryptInputs),
+            /// Print a random input for a day
+            Generate(CliGenerate),
+            /// Time a day on generated inputs of growing size and fit the
+            /// timings to complexity classes
+            Scale(CliScale),
+            /// Run all days against a time budget, one second by default
+            Budget(CliBudget),
+            /// Write an HTML page of the answers, timings and saved bench
+            /// history
+            Report(CliReport),
+            /// Browse, run and visualize the days in a full-screen terminal UI
+            #[cfg(feature = "tui")]
+            Dashboard,
+            /// Serve a JSON API for solving inputs on localhost
+            #[cfg(feature = "serve")]
+            Serve(CliServe),
+            /// Run a day loaded from a plugin
+            #[cfg(feature = "plugins")]
+            Plugin(CliPlugin),
+            /// Manage the answers `all` caches
+            Cache(CliCache),
         }
 
         impl CliDay {
-            pub fn run(&self) -> Result<(), Box<dyn Error>> {
+            fn all_days() -> Vec<(u32, Box<dyn Day>)> {
+                vec![$(
+                    $(#[$attr])*
+                    (
+                        day_number(stringify!($mod)),
+                        Box::new($cli::from_iter::<&[&str]>(&[])),
+                    ),
+                )+]
+            }
+
+            /// The numbers of the days built in.
+            #[cfg(feature = "ffi")]
+            fn day_numbers() -> Vec<u32> {
+                vec![$($(#[$attr])* day_number(stringify!($mod)),)+]
+            }
+
+            /// Day `n` reading its input from `path`.
+            fn day_with_input(n: u32, path: &Path) -> Option<Box<dyn Day>> {
+                match (n, path) {
+                    $($(#[$attr])*
+                    (n, path) if n == day_number(stringify!($mod)) => Some(Box::new(
+                        $cli::from_iter([std::ffi::OsStr::new(stringify!($mod)), path.as_os_str()]),
+                    )),)+
+                    _ => None,
+                }
+            }
+
+            /// The real input of every day. The examples are published with
+            /// the puzzles, so they are kept in plain text.
+            fn input_paths() -> Vec<PathBuf> {
+                vec![$($(#[$attr])* $mod::todays_input(),)+]
+            }
+
+            /// The input of day `n`, also for days only plugins solve.
+            fn input_for(n: u32) -> PathBuf {
+                match n {
+                    $($(#[$attr])*
+                    n if n == day_number(stringify!($mod)) => $mod::todays_input(),)+
+                    _ => input::resolve(
+                        n,
+                        Path::new(env!("CARGO_MANIFEST_DIR"))
+                            .join(format!("src/days/day{}/input.txt", n)),
+                    ),
+                }
+            }
+
+            #[cfg(feature = "plugins")]
+            fn plugin_days() -> Vec<PluginDay> {
+                plugin::load_all(Self::input_for)
+            }
+
+            #[cfg(feature = "plugins")]
+            fn find_plugin(opts: &CliPlugin) -> Result<PluginDay, Box<dyn Error>> {
+                let day = Self::plugin_days()
+                    .into_iter()
+                    .find(|p| p.name == opts.name)
+                    .ok_or_else(|| format!("no such plugin day: {}", opts.name))?;
+                Ok(match &opts.infile {
+                    Some(infile) => day.with_input(infile.clone()),
+                    None => day,
+                })
+            }
+
+            /// Each day with its real input followed by its examples.
+            #[cfg(feature = "tui")]
+            fn all_inputs() -> Vec<(u32, Vec<PathBuf>)> {
+                vec![$(
+                    $(#[$attr])*
+                    (
+                        day_number(stringify!($mod)),
+                        std::iter::once($mod::todays_input())
+                            .chain($mod::example_inputs())
+                            .collect(),
+                    ),
+                )+]
+            }
+
+            /// The parameters declared by each day this command runs.
+            fn params(&self) -> Vec<(u32, Vec<&'static dyn ParamSpec>)> {
+                match self {
+                    $($(#[$attr])*
+                    Self::$cli(day) => {
+                        vec![(day_number(stringify!($mod)), day.params())]
+                    })+
+                    Self::All | Self::Budget(_) | Self::Report(_) => {
+                        Self::all_params()
+                    }
+                    #[cfg(feature = "tui")]
+                    Self::Dashboard => Self::all_params(),
+                    #[cfg(feature = "serve")]
+                    Self::Serve(_) => Self::all_params(),
+                    Self::Generate(CliGenerate { day, .. })
+                    | Self::Scale(CliScale { day, .. }) => Self::all_days()
+                        .iter()
+                        .filter(|(n, _)| n == day)
+                        .map(|(n, day)| (*n, day.params()))
+                        .collect(),
+                    _ => Vec::new(),
+                }
+            }
+
+            /// The parameters declared by each day.
+            fn all_params() -> Vec<(u32, Vec<&'static dyn ParamSpec>)> {
+                Self::all_days()
+                    .iter()
+                    .map(|(n, day)| (*n, day.params()))
+                    .collect()
+            }
+
+            /// Run the command; `fps` is the speed of the animations it plays.
+            #[cfg_attr(not(feature = "tui"), allow(unused_variables))]
+            pub fn run(&self, fps: f32, use_cache: bool) -> Result<(), Box<dyn Error>> {
                 match self {
-                    $(Self::$cli(day) => {
+                    $($(#[$attr])*
+                    Self::$cli(day) => {
                         day.run_and_print()?;
                     })+
                     Self::All => {
-                        let all_clis: &[Box<dyn Day>] = &[$(Box::new($cli::from_iter::<&[&str]>(&[])),)+];
-                        let width = dimensions_stdout().map(|d| d.0).unwrap_or(60);
+                        let width = style::width().max(16);
                         let mut time = 0.0;
+                        let mut n_cached = 0;
+                        let mut cache = use_cache.then(Cache::load).transpose()?;
 
-                        println!("");
+                        println!();
 
-                        for (i, cli) in all_clis.iter().enumerate() {
-                            print!("===[ Day {:02} ]===", i + 1);
-                            println!("{}", repeat_char!('=', width - 16));
-                            let (t1, t2) = cli.run_and_print()?;
+                        for (n, cli) in &Self::all_days() {
+                            let banner = format!("===[ Day {:02} ]===", n);
+                            println!(
+                                "{}",
+                                THEME.banner.paint(banner + &repeat_char!('=', width - 16))
+                            );
+                            let Some(cache) = &mut cache else {
+                                let (t1, t2) = cli.run_and_print()?;
+                                time += t1 + t2;
+                                continue;
+                            };
+                            let (ts1, ts2) = cache.run(*n, cli.as_ref(), &Self::input_for(*n))?;
+                            print_parts(&ts1, &ts2);
+                            for ts in [&ts1, &ts2] {
+                                if ts.cached {
+                                    n_cached += 1;
+                                } else {
+                                    time += ts.time;
+                                }
+                            }
+                        }
+                        #[cfg(feature = "plugins")]
+                        for day in &Self::plugin_days() {
+                            let banner = format!("===[ Day {:02}: {} ]===", day.day, day.name);
+                            let fill = width.saturating_sub(banner.chars().count());
+                            println!("{}", THEME.banner.paint(banner + &repeat_char!('=', fill)));
+                            let (t1, t2) = day.run_and_print()?;
                             time += t1 + t2;
                         }
+                        if let Some(cache) = &cache {
+                            cache.save()?;
+                        }
 
-                        println!("{}\n", repeat_char!('=', width));
-                        println!("all answers in {} ms\n", time / 1.0e3);
+                        println!("{}\n", THEME.banner.paint(repeat_char!('=', width)));
+                        if n_cached > 0 {
+                            println!(
+                                "all answers in {} ms, {} of them cached\n",
+                                time / 1.0e3,
+                                n_cached
+                            );
+                        } else {
+                            println!("all answers in {} ms\n", time / 1.0e3);
+                        }
+                    }
+                    Self::List => {
+                        for (n, day) in Self::all_days() {
+                            println!("{}", THEME.heading.paint(format!("day{}", n)));
+                            for p in day.params() {
+                                println!(
+                                    "    {} {:>6}  {:<6}  {}",
+                                    THEME.highlight.paint(format!("{:<16}", p.name())),
+                                    p.default_value(),
+                                    p.type_name(),
+                                    THEME.muted.paint(p.help())
+                                );
+                            }
+                        }
+                        #[cfg(feature = "plugins")]
+                        for day in Self::plugin_days() {
+                            println!(
+                                "{} {}",
+                                THEME.heading.paint(format!("day{} {}", day.day, day.name)),
+                                THEME.muted.paint(format!("[plugin {}]", day.path.display()))
+                            );
+                        }
+                    }
+                    Self::EncryptInputs(opts) => {
+                        let paths = Self::input_paths();
+                        input::encrypt_inputs(
+                            paths.iter().map(PathBuf::as_path),
+                            opts.remove,
+                        )?;
+                    }
+                    Self::DecryptInputs(opts) => {
+                        let paths = Self::input_paths();
+                        input::decrypt_inputs(
+                            paths.iter().map(PathBuf::as_path),
+                            opts.remove,
+                        )?;
+                    }
+                    Self::Generate(opts) => {
+                        if opts.size == 0 {
+                            return Err("size must be at least 1".into());
+                        }
+                        let (_, day) = Self::all_days()
+                            .into_iter()
+                            .find(|(n, _)| *n == opts.day)
+                            .ok_or_else(|| format!("no such day: {}", opts.day))?;
+                        let mut rng = generate::rng(opts.seed);
+                        print!("{}", day.generate(&mut rng, opts.size)?);
+                    }
+                    Self::Scale(opts) => {
+                        let (_, day) = Self::all_days()
+                            .into_iter()
+                            .find(|(n, _)| *n == opts.day)
+                            .ok_or_else(|| format!("no such day: {}", opts.day))?;
+                        scale::run(opts, day.as_ref(), |path| {
+                            Self::day_with_input(opts.day, path).unwrap()
+                        })?;
+                    }
+                    Self::Budget(opts) => budget::run(opts, &Self::all_days())?,
+                    Self::Report(opts) => report::run(opts, &Self::all_days())?,
+                    #[cfg(feature = "tui")]
+                    Self::Dashboard => dashboard::run(
+                        Self::all_inputs(),
+                        |n, path| Self::day_with_input(n, path).unwrap(),
+                        fps,
+                    )?,
+                    Self::Cache(CliCache::Clear) => cache::clear()?,
+                    #[cfg(feature = "plugins")]
+                    Self::Plugin(opts) => {
+                        Self::find_plugin(opts)?.run_and_print()?;
+                    }
+                    #[cfg(feature = "serve")]
+                    Self::Serve(opts) => {
+                        serve::run(opts, Self::all_params(), Self::day_with_input)?
                     }
                 }
                 Ok(())
@@ -121,16 +686,23 @@ macro_rules! decl_day {
 
             pub fn bench(&self) -> Result<(), Box<dyn Error>> {
                 const N_WARMUPS: usize = 5;
+                // The average milliseconds of a part and its answer.
                 macro_rules! avg_part_with {
                     ($runner:expr) => {{
                         let mut t = f32::MAX;
+                        let mut answer = None;
                         // Warmup
                         for _ in 0..N_WARMUPS {
-                            t = t.min($runner.time);
+                            let ts = $runner;
+                            t = t.min(ts.time);
+                            answer = ts.solution.ok();
                         }
                         let n = (1.0e6 / t) as usize;
                         if n < 3 {
-                            println!("warning: part will only be run {} times", n);
+                            println!(
+                                "{}",
+                                THEME.error.paint(format!("warning: part will only be run {} times", n))
+                            );
                         }
                         // Sum runtimes
                         t = 0.0;
@@ -138,40 +710,69 @@ macro_rules! decl_day {
                             t += $runner.time;
                         }
                         // Average
-                        t / n as f32
+                        (t / n as f32 / 1.0e3, answer)
                     }};
                 }
 
                 match self {
-                    $(Self::$cli(day) => {
-                        println!("");
-                        println!("Part    {:>10}", "Avg (ms)");
+                    $($(#[$attr])*
+                    Self::$cli(day) => {
+                        println!();
+                        println!("{}", THEME.heading.paint(format!("Part    {:>10}", "Avg (ms)")));
                         println!("--------{}", repeat_char!('-', 10));
-                        let avg1 = avg_part_with!(day.run_part1()?) / 1.0e3;
+                        let (avg1, part1) = avg_part_with!(day.run_part1()?);
                         println!("   1    {:10.4}", avg1);
-                        let avg2 = avg_part_with!(day.run_part2()?) / 1.0e3;
+                        let (avg2, part2) = avg_part_with!(day.run_part2()?);
                         println!("   2    {:10.4}", avg2);
                         println!("--------{}", repeat_char!('-', 10));
                         println!("        {:10.4}", avg1 + avg2);
-                        println!("");
+                        println!();
+                        history::Run::now(vec![history::Record {
+                            day: day_number(stringify!($mod)),
+                            times: [avg1 as f64, avg2 as f64],
+                            part1,
+                            part2,
+                        }])
+                        .save()?;
                     })+
+                    #[cfg(feature = "plugins")]
+                    Self::Plugin(opts) => {
+                        let day = Self::find_plugin(opts)?;
+                        println!();
+                        println!("{}", THEME.heading.paint(format!("Part    {:>10}", "Avg (ms)")));
+                        println!("--------{}", repeat_char!('-', 10));
+                        let (avg1, _) = avg_part_with!(day.run_part1()?);
+                        println!("   1    {:10.4}", avg1);
+                        let (avg2, _) = avg_part_with!(day.run_part2()?);
+                        println!("   2    {:10.4}", avg2);
+                        println!("--------{}", repeat_char!('-', 10));
+                        println!("        {:10.4}", avg1 + avg2);
+                        println!();
+                    }
                     Self::All => {
-                        let all_clis: &[Box<dyn Day>] =
-                            &[$(Box::new($cli::from_iter::<&[&str]>(&[]))),+];
-
-                        println!("");
+                        println!();
 
-                        println!("Day    Part 1 Avg    Part 2 Avg    Total (ms)");
+                        println!(
+                            "{}",
+                            THEME.heading.paint("Day    Part 1 Avg    Part 2 Avg    Total (ms)")
+                        );
                         println!("---------------------------------------------");
                         let mut total = 0.0;
-                        for (i, cli) in all_clis.iter().enumerate() {
-                            let avg1 = avg_part_with!(cli.run_part1()?) / 1.0e3;
-                            let avg2 = avg_part_with!(cli.run_part2()?) / 1.0e3;
+                        let mut records = Vec::new();
+                        for (n, cli) in &Self::all_days() {
+                            let (avg1, part1) = avg_part_with!(cli.run_part1()?);
+                            let (avg2, part2) = avg_part_with!(cli.run_part2()?);
+                            records.push(history::Record {
+                                day: *n,
+                                times: [avg1 as f64, avg2 as f64],
+                                part1,
+                                part2,
+                            });
                             let sum = avg1 + avg2;
                             total += sum;
                             println!(
                                 "{:3}    {:>10.4}    {:>10.4}    {:>10.4}",
-                                i + 1, avg1, avg2, sum
+                                n, avg1, avg2, sum
                             );
                         }
                         println!("---------------------------------------------");
@@ -183,8 +784,29 @@ macro_rules! decl_day {
                             total
                         );
 
-                        println!("");
+                        // Plugins are listed apart, to compare with the
+                        // built-in days rather than to add to their total.
+                        #[cfg(feature = "plugins")]
+                        {
+                            let plugins = Self::plugin_days();
+                            if !plugins.is_empty() {
+                                println!();
+                                println!("{}", THEME.heading.paint("Plugins"));
+                            }
+                            for day in &plugins {
+                                let (avg1, _) = avg_part_with!(day.run_part1()?);
+                                let (avg2, _) = avg_part_with!(day.run_part2()?);
+                                println!(
+                                    "{:3}    {:>10.4}    {:>10.4}    {:>10.4}    {}",
+                                    day.day, avg1, avg2, avg1 + avg2, day.name
+                                );
+                            }
+                        }
+
+                        println!();
+                        history::Run::now(records).save()?;
                     }
+                    _ => return Err("only days can be benchmarked".into()),
                 }
                 Ok(())
             }
@@ -193,48 +815,104 @@ macro_rules! decl_day {
 }
 
 decl_day! {
+    #[cfg(feature = "day1")]
     day1 => Day1;
+    #[cfg(feature = "day2")]
     day2 => Day2;
+    #[cfg(feature = "day3")]
     day3 => Day3;
+    #[cfg(feature = "day4")]
     day4 => Day4;
+    #[cfg(feature = "day5")]
     day5 => Day5;
+    #[cfg(feature = "day6")]
     day6 => Day6;
+    #[cfg(feature = "day7")]
     day7 => Day7;
+    #[cfg(feature = "day8")]
     day8 => Day8;
+    #[cfg(feature = "day9")]
     day9 => Day9;
+    #[cfg(feature = "day10")]
     day10 => Day10;
+    #[cfg(feature = "day11")]
     day11 => Day11;
+    #[cfg(feature = "day12")]
     day12 => Day12;
+    #[cfg(feature = "day13")]
     day13 => Day13;
+    #[cfg(feature = "day14")]
     day14 => Day14;
 }
 
+#[cfg(feature = "any-day")]
 macro_rules! todays_input {
-    ($symbol:ident) => {
-        lazy_static::lazy_static! {
-            static ref $symbol: String = {
-                let this_file = PathBuf::from(concat!(
-                    env!("CARGO_MANIFEST_DIR"),
-                    "/",
-                    file!()
-                ));
-                match this_file.parent() {
-                    Some(dir) =>
-                        dir.join("input.txt").to_string_lossy().to_string(),
-                    None => panic!(
-                        "failed to get parent of path: {}",
-                        this_file.display()
-                    ),
-                }
-            };
+    ($day:ident) => {
+        fn module_dir() -> PathBuf {
+            let this_file = PathBuf::from(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/",
+                file!()
+            ));
+            match this_file.parent() {
+                Some(dir) => dir.to_path_buf(),
+                None => panic!(
+                    "failed to get parent of path: {}",
+                    this_file.display()
+                ),
+            }
+        }
+
+        /// The day's input under the configured inputs root, or else the
+        /// `input.txt` next to this module.
+        pub(super) fn todays_input() -> PathBuf {
+            let fallback = module_dir().join("input.txt");
+            $crate::input::resolve(super::day_number(module_path!()), fallback)
+        }
+
+        /// The puzzle's examples next to this module, such as
+        /// `test-input.txt`.
+        #[cfg(feature = "tui")]
+        pub(super) fn example_inputs() -> Vec<PathBuf> {
+            let mut paths: Vec<_> = std::fs::read_dir(module_dir())
+                .into_iter()
+                .flatten()
+                .filter_map(|entry| Some(entry.ok()?.path()))
+                .filter(|path| {
+                    path.file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| name.ends_with("-input.txt"))
+                })
+                .collect();
+            paths.sort();
+            paths
+        }
+
+        impl $day {
+            fn infile(&self) -> PathBuf {
+                self.infile.clone().unwrap_or_else(todays_input)
+            }
         }
     };
 }
-pub(self) use todays_input;
+#[cfg(feature = "any-day")]
+pub(crate) use todays_input;
+
+#[cfg(feature = "any-day")]
+fn day_number(module_path: &str) -> u32 {
+    module_path
+        .rsplit("::")
+        .next()
+        .and_then(|m| m.strip_prefix("day"))
+        .and_then(|n| n.parse().ok())
+        .unwrap_or_else(|| panic!("not a day module: {}", module_path))
+}
 
+#[cfg(feature = "any-day")]
 macro_rules! ANSWER {
     ($value:expr) => {
-        Ok(Box::new($value))
+        Ok($crate::days::Answer::from($value))
     };
 }
-pub(self) use ANSWER;
+#[cfg(feature = "any-day")]
+pub(crate) use ANSWER;
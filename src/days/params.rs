@@ -0,0 +1,187 @@
+#[cfg(feature = "params")]
+use std::{any::type_name, fmt::Display};
+use std::{error::Error, str::FromStr, sync::OnceLock};
+
+#[cfg(feature = "params")]
+use super::day_number;
+
+static OVERRIDES: OnceLock<Vec<ParamArg>> = OnceLock::new();
+
+/// A `--param` argument, either `name=value` to set the parameter for every
+/// day that declares it or `dayN.name=value` to set it for day N only.
+#[derive(Clone, Debug)]
+pub struct ParamArg {
+    day: Option<u32>,
+    name: String,
+    value: String,
+}
+
+impl FromStr for ParamArg {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (key, value) = s
+            .split_once('=')
+            .ok_or_else(|| format!("expected name=value: {}", s))?;
+        let (day, name) = match key.split_once('.') {
+            Some((day, name)) => match day_number_of(day) {
+                Some(n) => (Some(n), name),
+                None => return Err(format!("invalid day: {}", day)),
+            },
+            None => (None, key),
+        };
+        Ok(Self {
+            day,
+            name: name.trim().to_string(),
+            value: value.trim().to_string(),
+        })
+    }
+}
+
+fn day_number_of(s: &str) -> Option<u32> {
+    s.trim().strip_prefix("day").and_then(|n| n.parse().ok())
+}
+
+/// A tunable puzzle constant. Declare them as statics with [`param!`] and
+/// list them in [`Day::params`](super::Day::params).
+#[cfg(feature = "params")]
+pub struct Param<T: 'static> {
+    module: &'static str,
+    name: &'static str,
+    default: T,
+    help: &'static str,
+}
+
+#[cfg(feature = "params")]
+impl<T> Param<T> {
+    pub const fn new(
+        module: &'static str,
+        name: &'static str,
+        default: T,
+        help: &'static str,
+    ) -> Self {
+        Self { module, name, default, help }
+    }
+}
+
+#[cfg(feature = "params")]
+impl<T> Param<T>
+where
+    T: Copy + FromStr,
+{
+    /// The value given on the command line, or else the default.
+    pub fn get(&self) -> Result<T, Box<dyn Error>> {
+        match lookup(day_number(self.module), self.name) {
+            Some(value) => parse_value::<T>(self.name, value),
+            None => Ok(self.default),
+        }
+    }
+}
+
+pub trait ParamSpec: Sync {
+    fn name(&self) -> &'static str;
+
+    fn help(&self) -> &'static str;
+
+    fn type_name(&self) -> &'static str;
+
+    fn default_value(&self) -> String;
+
+    fn validate(&self, value: &str) -> Result<(), Box<dyn Error>>;
+}
+
+#[cfg(feature = "params")]
+impl<T> ParamSpec for Param<T>
+where
+    T: Copy + Display + FromStr + Sync,
+{
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn help(&self) -> &'static str {
+        self.help
+    }
+
+    fn type_name(&self) -> &'static str {
+        type_name::<T>()
+    }
+
+    fn default_value(&self) -> String {
+        self.default.to_string()
+    }
+
+    fn validate(&self, value: &str) -> Result<(), Box<dyn Error>> {
+        parse_value::<T>(self.name, value).map(|_| ())
+    }
+}
+
+#[cfg(feature = "params")]
+fn parse_value<T: FromStr>(
+    name: &str,
+    value: &str,
+) -> Result<T, Box<dyn Error>> {
+    value.parse().map_err(|_| {
+        format!(
+            "invalid value for parameter {} ({}): {}",
+            name,
+            type_name::<T>(),
+            value
+        )
+        .into()
+    })
+}
+
+/// The value of `spec` for day `day`, as given on the command line or else
+/// the default.
+pub fn value_of(day: u32, spec: &dyn ParamSpec) -> String {
+    lookup(day, spec.name()).map_or_else(|| spec.default_value(), String::from)
+}
+
+fn lookup(day: u32, name: &str) -> Option<&'static str> {
+    OVERRIDES
+        .get()?
+        .iter()
+        .rev()
+        .find(|a| a.name == name && a.day.is_none_or(|d| d == day))
+        .map(|a| a.value.as_str())
+}
+
+/// Check `args` against the parameters declared by the days about to run,
+/// then make them visible to [`Param::get`].
+pub fn init(
+    args: Vec<ParamArg>,
+    days: &[(u32, Vec<&'static dyn ParamSpec>)],
+) -> Result<(), Box<dyn Error>> {
+    for arg in &args {
+        let mut found = false;
+        for (day, specs) in days {
+            if arg.day.is_some_and(|d| d != *day) {
+                continue;
+            }
+            if let Some(spec) = specs.iter().find(|s| s.name() == arg.name) {
+                spec.validate(&arg.value)?;
+                found = true;
+            }
+        }
+        if !found {
+            return Err(format!(
+                "unknown parameter: {} (see the `list` command)",
+                arg.name
+            )
+            .into());
+        }
+    }
+
+    let _ = OVERRIDES.set(args);
+    Ok(())
+}
+
+#[cfg(feature = "params")]
+macro_rules! param {
+    ($name:literal, $default:expr, $help:literal) => {
+        $crate::days::params::Param::new(module_path!(), $name, $default, $help)
+    };
+}
+#[cfg(feature = "params")]
+pub(crate) use param;
@@ -0,0 +1,177 @@
+use std::{
+    error::Error,
+    ffi::{c_char, CStr},
+    fs,
+    path::{Path, PathBuf},
+    slice,
+    sync::Arc,
+};
+
+use libloading::Library;
+
+use super::{Answer, Day, PartResult};
+use crate::{
+    config, input,
+    plugin_abi::{PluginAnswer, PluginDays, ABI_VERSION},
+};
+
+const ENTRY_POINT: &[u8] = b"aoc_plugin_days";
+const PLUGINS_DIR: &str = "plugins";
+
+type EntryPoint = unsafe extern "C" fn() -> *const PluginDays;
+
+/// A day solved by a plugin.
+pub struct PluginDay {
+    pub day: u32,
+    pub name: String,
+    /// The library file it came from.
+    pub path: PathBuf,
+    infile: PathBuf,
+    solve: unsafe extern "C" fn(u32, *const u8, usize) -> PluginAnswer,
+    free_text: unsafe extern "C" fn(*mut c_char),
+    /// Keeps the functions above loaded.
+    _library: Arc<Library>,
+}
+
+impl PluginDay {
+    /// The same solution reading `infile` instead.
+    pub fn with_input(&self, infile: PathBuf) -> Self {
+        Self {
+            name: self.name.clone(),
+            path: self.path.clone(),
+            infile,
+            _library: self._library.clone(),
+            ..*self
+        }
+    }
+
+    fn solve(&self, part: u32) -> PartResult {
+        let file = input::load(&self.infile)?;
+        let text = file.text()?;
+
+        // SAFETY: the plugin promises `solve` reads `len` bytes of `input`
+        // and returns a NUL-terminated text that is ours until freed.
+        let answer = unsafe {
+            let answer = (self.solve)(part, text.as_ptr(), text.len());
+            if answer.text.is_null() {
+                return Err(
+                    format!("plugin {} returned no text", self.name).into()
+                );
+            }
+            let text =
+                CStr::from_ptr(answer.text).to_string_lossy().into_owned();
+            (self.free_text)(answer.text);
+            (answer.ok, text)
+        };
+
+        match answer {
+            (true, text) => Ok(parse_answer(text)),
+            (false, message) => Err(message.into()),
+        }
+    }
+}
+
+impl Day for PluginDay {
+    fn part1(&self) -> PartResult {
+        self.solve(1)
+    }
+
+    fn part2(&self) -> PartResult {
+        self.solve(2)
+    }
+}
+
+fn parse_answer(text: String) -> Answer {
+    if text.contains('\n') {
+        Answer::Grid(text.lines().map(str::to_string).collect())
+    } else if let Ok(n) = text.parse() {
+        Answer::Integer(n)
+    } else {
+        Answer::Text(text)
+    }
+}
+
+/// The configured plugins directory, or `plugins` in the crate root.
+fn plugins_dir() -> PathBuf {
+    config::get().plugins_dir.clone().unwrap_or_else(|| {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join(PLUGINS_DIR)
+    })
+}
+
+/// The days of every plugin in the plugins directory, reading the inputs
+/// that `input_for` gives for their day numbers. Libraries that fail to
+/// load are reported and skipped.
+pub fn load_all(input_for: impl Fn(u32) -> PathBuf) -> Vec<PluginDay> {
+    let mut paths: Vec<_> = match fs::read_dir(plugins_dir()) {
+        Ok(entries) => entries
+            .filter_map(|entry| Some(entry.ok()?.path()))
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|ext| ext == std::env::consts::DLL_EXTENSION)
+            })
+            .collect(),
+        Err(_) => return Vec::new(),
+    };
+    paths.sort();
+
+    let mut days = Vec::new();
+    for path in paths {
+        match load(&path, &input_for) {
+            Ok(loaded) => days.extend(loaded),
+            Err(e) => eprintln!("skipping plugin {}: {}", path.display(), e),
+        }
+    }
+    days
+}
+
+fn load(
+    path: &Path,
+    input_for: impl Fn(u32) -> PathBuf,
+) -> Result<Vec<PluginDay>, Box<dyn Error>> {
+    // SAFETY: loading runs the library's initializers, which we trust as
+    // much as the rest of the plugin.
+    let library = Arc::new(unsafe { Library::new(path)? });
+    // SAFETY: the entry point has the signature of the plugin ABI.
+    let plugin = unsafe {
+        let entry = library.get::<EntryPoint>(ENTRY_POINT)?;
+        entry()
+    };
+    if plugin.is_null() {
+        return Err("aoc_plugin_days returned null".into());
+    }
+    // SAFETY: checked for null; the plugin keeps it alive while loaded.
+    let plugin = unsafe { &*plugin };
+    if plugin.abi_version != ABI_VERSION {
+        return Err(format!(
+            "it was built for plugin ABI {}, not {}",
+            plugin.abi_version, ABI_VERSION
+        )
+        .into());
+    }
+
+    let specs = if plugin.len == 0 {
+        &[][..]
+    } else {
+        // SAFETY: the plugin promises `days` points to `len` specs.
+        unsafe { slice::from_raw_parts(plugin.days, plugin.len) }
+    };
+    specs
+        .iter()
+        .map(|spec| {
+            if spec.name.is_null() {
+                return Err("a day has no name".into());
+            }
+            // SAFETY: checked for null; the name is a static C string.
+            let name = unsafe { CStr::from_ptr(spec.name) };
+            Ok(PluginDay {
+                day: spec.day,
+                name: name.to_string_lossy().into_owned(),
+                path: path.to_path_buf(),
+                infile: input_for(spec.day),
+                solve: spec.solve,
+                free_text: plugin.free_text,
+                _library: library.clone(),
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,304 @@
+use std::{error::Error, fmt::Write as _, fs};
+
+use super::{
+    history::{self, Run},
+    Answer, CliReport, Day,
+};
+
+const CHART_WIDTH: f64 = 480.0;
+const CHART_HEIGHT: f64 = 160.0;
+/// Room around the plot for the axis labels.
+const CHART_MARGIN: f64 = 24.0;
+const PART_COLORS: [&str; 2] = ["#1f77b4", "#ff7f0e"];
+/// Side of a lit cell of a grid answer, in pixels.
+const CELL_SIZE: usize = 6;
+
+const STYLE: &str = "body { font-family: sans-serif; margin: 2em; color: #222; }
+table { border-collapse: collapse; }
+th, td { padding: 0.3em 0.8em; border-bottom: 1px solid #ddd; text-align: left; }
+td.time { text-align: right; font-variant-numeric: tabular-nums; }
+.error { color: #c00; }
+.muted { color: #888; font-size: 0.9em; }
+.charts { display: flex; flex-wrap: wrap; gap: 1em; }
+.chart h3 { margin: 0.5em 0 0 0; }";
+
+/// One day's line of the answers table.
+struct Row {
+    day: u32,
+    /// Milliseconds.
+    times: [f64; 2],
+    answers: [Result<Answer, String>; 2],
+}
+
+/// Write a self-contained HTML page with the answers and timings of all
+/// days, and charts of their timings over the saved bench runs.
+pub fn run(
+    opts: &CliReport,
+    days: &[(u32, Box<dyn Day>)],
+) -> Result<(), Box<dyn Error>> {
+    let runs = history::load()?;
+
+    let (rows, source) = if opts.saved {
+        // A run may hold only some days, so take each day's latest record.
+        let last = runs
+            .last()
+            .ok_or("there are no saved bench runs; run with --bench first")?;
+        let mut rows: Vec<Row> = Vec::new();
+        for r in runs.iter().rev().flat_map(|run| &run.days) {
+            if rows.iter().all(|row| row.day != r.day) {
+                rows.push(Row {
+                    day: r.day,
+                    times: r.times,
+                    answers: [&r.part1, &r.part2].map(|answer| {
+                        answer.clone().ok_or_else(|| "failed".to_string())
+                    }),
+                });
+            }
+        }
+        rows.sort_by_key(|row| row.day);
+        let source = format!(
+            "the latest bench run of each day, up to {}",
+            date(last.timestamp)
+        );
+        (rows, source)
+    } else {
+        let mut rows = Vec::new();
+        for (n, day) in days {
+            let (ts1, ts2) = day.run()?;
+            rows.push(Row {
+                day: *n,
+                times: [ts1.time as f64 / 1.0e3, ts2.time as f64 / 1.0e3],
+                answers: [ts1.solution, ts2.solution]
+                    .map(|s| s.map_err(|e| e.to_string())),
+            });
+        }
+        (rows, "a single run".to_string())
+    };
+
+    let mut html = String::new();
+    writeln!(
+        html,
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>Advent of Code 2021</title>\n<style>\n{}\n</style>\n</head>\n\
+         <body>\n<h1>Advent of Code 2021</h1>",
+        STYLE
+    )?;
+    writeln!(
+        html,
+        "<p class=\"muted\">Answers and timings from {}.</p>",
+        escape(&source)
+    )?;
+    write_table(&mut html, &rows)?;
+    write_history(&mut html, &runs)?;
+    writeln!(html, "</body>\n</html>")?;
+
+    fs::write(&opts.output, html)?;
+    println!("wrote the report to {}", opts.output.display());
+    Ok(())
+}
+
+fn write_table(html: &mut String, rows: &[Row]) -> Result<(), Box<dyn Error>> {
+    writeln!(
+        html,
+        "<h2>Answers</h2>\n<table>\n<tr><th>Day</th><th>Part 1</th>\
+         <th>ms</th><th>Part 2</th><th>ms</th></tr>"
+    )?;
+    let mut total = 0.0;
+    for row in rows {
+        write!(html, "<tr><td>{}</td>", row.day)?;
+        for (answer, time) in row.answers.iter().zip(row.times) {
+            let cell = match answer {
+                Ok(answer) => answer_html(answer),
+                Err(e) => format!("<span class=\"error\">{}</span>", escape(e)),
+            };
+            write!(
+                html,
+                "<td>{}</td><td class=\"time\">{:.3}</td>",
+                cell, time
+            )?;
+            total += time;
+        }
+        writeln!(html, "</tr>")?;
+    }
+    writeln!(
+        html,
+        "<tr><th colspan=\"4\">Total</th><td class=\"time\">{:.3}</td></tr>\n\
+         </table>",
+        total
+    )?;
+    Ok(())
+}
+
+/// Grid answers are drawn, with the letters they read as underneath.
+fn answer_html(answer: &Answer) -> String {
+    let rows = match answer {
+        Answer::Grid(rows) => rows,
+        _ => return format!("<code>{}</code>", escape(&answer.to_string())),
+    };
+
+    let width = rows.iter().map(|r| r.chars().count()).max().unwrap_or(0);
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\">",
+        width * CELL_SIZE,
+        rows.len() * CELL_SIZE
+    );
+    for (y, row) in rows.iter().enumerate() {
+        for (x, _) in row.chars().enumerate().filter(|&(_, c)| c == '#') {
+            svg += &format!(
+                "<rect x=\"{}\" y=\"{}\" width=\"{s}\" height=\"{s}\"/>",
+                x * CELL_SIZE,
+                y * CELL_SIZE,
+                s = CELL_SIZE
+            );
+        }
+    }
+    svg += "</svg>";
+    if let Ok(letters) = answer.submission() {
+        svg += &format!("<br><code>{}</code>", escape(&letters));
+    }
+    svg
+}
+
+/// A chart per day of the time of each part across the saved runs.
+fn write_history(
+    html: &mut String,
+    runs: &[Run],
+) -> Result<(), Box<dyn Error>> {
+    writeln!(html, "<h2>Timing history</h2>")?;
+    let mut days: Vec<_> =
+        runs.iter().flat_map(|run| run.days.iter().map(|r| r.day)).collect();
+    days.sort_unstable();
+    days.dedup();
+    if days.is_empty() {
+        writeln!(
+            html,
+            "<p class=\"muted\">No saved bench runs yet; they are saved by \
+             running with --bench.</p>"
+        )?;
+        return Ok(());
+    }
+
+    writeln!(
+        html,
+        "<p><span style=\"color: {}\">■ part 1</span> \
+         <span style=\"color: {}\">■ part 2</span></p>\n<div class=\"charts\">",
+        PART_COLORS[0], PART_COLORS[1]
+    )?;
+    for day in days {
+        // The runs that timed this day, with when they ran.
+        let points: Vec<_> = runs
+            .iter()
+            .filter_map(|run| {
+                let record = run.days.iter().find(|r| r.day == day)?;
+                Some((run.timestamp, record.times))
+            })
+            .collect();
+        writeln!(
+            html,
+            "<div class=\"chart\"><h3>Day {}</h3>\n{}</div>",
+            day,
+            chart(&points)
+        )?;
+    }
+    writeln!(html, "</div>")?;
+    Ok(())
+}
+
+/// A line chart of the two parts' times, one point per run, drawn from 0 up
+/// to the slowest time.
+fn chart(points: &[(u64, [f64; 2])]) -> String {
+    let max = points
+        .iter()
+        .flat_map(|(_, times)| *times)
+        .fold(0.0, f64::max)
+        .max(1.0e-3);
+    let plot_width = CHART_WIDTH - 2.0 * CHART_MARGIN;
+    let plot_height = CHART_HEIGHT - 2.0 * CHART_MARGIN;
+    let x = |i: usize| {
+        let step = plot_width / (points.len().max(2) - 1) as f64;
+        CHART_MARGIN + i as f64 * step
+    };
+    let y = |t: f64| CHART_MARGIN + plot_height * (1.0 - t / max);
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" \
+         height=\"{h}\" font-size=\"10\">\n\
+         <line x1=\"{m}\" y1=\"{m}\" x2=\"{m}\" y2=\"{b}\" stroke=\"#999\"/>\n\
+         <line x1=\"{m}\" y1=\"{b}\" x2=\"{r}\" y2=\"{b}\" stroke=\"#999\"/>\n\
+         <text x=\"{m}\" y=\"{t}\">{max:.3} ms</text>\n",
+        w = CHART_WIDTH,
+        h = CHART_HEIGHT,
+        m = CHART_MARGIN,
+        b = CHART_HEIGHT - CHART_MARGIN,
+        r = CHART_WIDTH - CHART_MARGIN,
+        t = CHART_MARGIN - 6.0,
+        max = max,
+    );
+    for (part, color) in PART_COLORS.iter().enumerate() {
+        let line: Vec<_> = points
+            .iter()
+            .enumerate()
+            .map(|(i, (_, times))| format!("{:.1},{:.1}", x(i), y(times[part])))
+            .collect();
+        svg += &format!(
+            "<polyline fill=\"none\" stroke=\"{}\" stroke-width=\"2\" \
+             points=\"{}\"/>\n",
+            color,
+            line.join(" ")
+        );
+        for (i, (timestamp, times)) in points.iter().enumerate() {
+            svg += &format!(
+                "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"3\" fill=\"{}\">\
+                 <title>{}: {:.3} ms</title></circle>\n",
+                x(i),
+                y(times[part]),
+                color,
+                date(*timestamp),
+                times[part]
+            );
+        }
+    }
+    if let (Some((first, _)), Some((last, _))) = (points.first(), points.last())
+    {
+        svg += &format!(
+            "<text x=\"{m}\" y=\"{y}\">{}</text>\n\
+             <text x=\"{r}\" y=\"{y}\" text-anchor=\"end\">{}</text>\n",
+            date(*first),
+            date(*last),
+            m = CHART_MARGIN,
+            r = CHART_WIDTH - CHART_MARGIN,
+            y = CHART_HEIGHT - 8.0,
+        );
+    }
+    svg + "</svg>"
+}
+
+/// `YYYY-MM-DD HH:MM UTC` of a Unix timestamp.
+fn date(timestamp: u64) -> String {
+    let (days, secs) = (timestamp / 86400, timestamp % 86400);
+    // Days to a civil date, from Howard Hinnant's `civil_from_days`.
+    let z = days as i64 + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + (month <= 2) as i64;
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        secs / 3600,
+        secs % 3600 / 60
+    )
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,263 @@
+use std::{
+    env,
+    error::Error,
+    fmt::Write as _,
+    fs,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use super::{generate, CliScale, Day, TimedSolution};
+use crate::style::THEME;
+
+/// Each part is repeated until it has run for this long, keeping the fastest
+/// run, so that short parts are not lost in timer noise.
+const MIN_SAMPLE_TIME: Duration = Duration::from_millis(200);
+const MAX_RUNS: usize = 20;
+
+/// The timings of both parts on one generated input.
+struct Sample {
+    size: usize,
+    bytes: usize,
+    /// Milliseconds.
+    times: [f64; 2],
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Complexity {
+    Constant,
+    Logarithmic,
+    Linear,
+    Linearithmic,
+    Quadratic,
+    Cubic,
+    Exponential,
+}
+
+impl Complexity {
+    /// The classes of the form `c * f(n)`.
+    const POLYNOMIAL: [Self; 6] = [
+        Self::Constant,
+        Self::Logarithmic,
+        Self::Linear,
+        Self::Linearithmic,
+        Self::Quadratic,
+        Self::Cubic,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Constant => "O(1)",
+            Self::Logarithmic => "O(log n)",
+            Self::Linear => "O(n)",
+            Self::Linearithmic => "O(n log n)",
+            Self::Quadratic => "O(n²)",
+            Self::Cubic => "O(n³)",
+            Self::Exponential => "O(cⁿ)",
+        }
+    }
+
+    /// `ln f(n)`.
+    fn ln_f(self, n: f64) -> f64 {
+        match self {
+            Self::Constant => 0.0,
+            Self::Logarithmic => n.ln().max(1.0).ln(),
+            Self::Linear => n.ln(),
+            Self::Linearithmic => n.ln() + n.ln().max(1.0).ln(),
+            Self::Quadratic => 2.0 * n.ln(),
+            Self::Cubic => 3.0 * n.ln(),
+            Self::Exponential => n,
+        }
+    }
+}
+
+/// How well a complexity class explains a curve.
+struct Fit {
+    complexity: Complexity,
+    /// Root mean square of the residuals of `ln t`.
+    error: f64,
+}
+
+/// Time `opts.day` on generated inputs of doubling size, then fit the
+/// timings of each part against the usual complexity classes.
+/// `with_input` makes the day read a given input file.
+pub fn run(
+    opts: &CliScale,
+    day: &dyn Day,
+    with_input: impl Fn(&Path) -> Box<dyn Day>,
+) -> Result<(), Box<dyn Error>> {
+    if opts.max_size == 0 {
+        return Err("max size must be at least 1".into());
+    }
+    let path = env::temp_dir().join(format!(
+        "aoc-scale-{}-day{}.txt",
+        std::process::id(),
+        opts.day
+    ));
+
+    println!();
+    println!(
+        "{}",
+        THEME.heading.paint(format!(
+            "{:>6}  {:>10}  {:>12}  {:>12}",
+            "Size", "Bytes", "Part 1 (ms)", "Part 2 (ms)"
+        ))
+    );
+    let mut samples = Vec::new();
+    let mut size = 1;
+    while size <= opts.max_size {
+        let mut rng = generate::rng(opts.seed);
+        let input = day.generate(&mut rng, size)?;
+        fs::write(&path, &input)?;
+        let timed = with_input(&path);
+        let times =
+            [time_part(|| timed.run_part1()), time_part(|| timed.run_part2())];
+        fs::remove_file(&path)?;
+        let [t1, t2] = times;
+        let times = [t1?, t2?];
+
+        println!(
+            "{:>6}  {:>10}  {:>12.4}  {:>12.4}",
+            size,
+            input.len(),
+            times[0],
+            times[1]
+        );
+        samples.push(Sample { size, bytes: input.len(), times });
+
+        if times.iter().any(|&t| t > opts.max_time * 1.0e3) {
+            println!(
+                "{}",
+                THEME.muted.paint(format!(
+                    "[stopping: a part took over {} s]",
+                    opts.max_time
+                ))
+            );
+            break;
+        }
+        size *= 2;
+    }
+    println!();
+
+    if samples.len() < 3 {
+        println!("{}", THEME.muted.paint("[too few sizes to fit a curve]"));
+    } else {
+        for part in 0..2 {
+            let points: Vec<_> = samples
+                .iter()
+                .map(|s| (s.bytes as f64, s.times[part]))
+                .collect();
+            print_fits(part + 1, &points);
+        }
+    }
+
+    if let Some(export) = &opts.export {
+        let mut csv = String::from("size,bytes,part1_ms,part2_ms\n");
+        for s in &samples {
+            writeln!(
+                csv,
+                "{},{},{},{}",
+                s.size, s.bytes, s.times[0], s.times[1]
+            )?;
+        }
+        fs::write(export, csv)?;
+        println!("exported the timings to {}", export.display());
+    }
+    Ok(())
+}
+
+/// The fastest of several runs of a part, in milliseconds.
+fn time_part(
+    run: impl Fn() -> Result<TimedSolution, Box<dyn Error>>,
+) -> Result<f64, Box<dyn Error>> {
+    let start = Instant::now();
+    let mut best = f64::MAX;
+    for _ in 0..MAX_RUNS {
+        let timed = run()?;
+        timed.solution?;
+        best = best.min(timed.time as f64 / 1.0e3);
+        if start.elapsed() >= MIN_SAMPLE_TIME {
+            break;
+        }
+    }
+    Ok(best)
+}
+
+fn print_fits(part: usize, points: &[(f64, f64)]) {
+    let fits = fit(points);
+    let (exponent, _) =
+        regression(points.iter().map(|&(n, t)| (n.ln(), t.ln())));
+    println!(
+        "{} best fit {} {}",
+        THEME.heading.paint(format!("Part {}:", part)),
+        THEME.answer.paint(fits[0].complexity.name()),
+        THEME.muted.paint(format!("(t ∝ n^{:.2})", exponent))
+    );
+    for f in &fits {
+        println!(
+            "    {:<12} {}",
+            f.complexity.name(),
+            THEME.muted.paint(format!("error {:.3}", f.error))
+        );
+    }
+    println!();
+}
+
+/// The complexity classes sorted from best to worst fit of `points`, which
+/// are pairs of input size and time. Timings are compared in log space so
+/// that small and large inputs weigh the same.
+fn fit(points: &[(f64, f64)]) -> Vec<Fit> {
+    let rms = |residuals: Vec<f64>| {
+        (residuals.iter().map(|r| r * r).sum::<f64>() / residuals.len() as f64)
+            .sqrt()
+    };
+
+    let mut fits: Vec<_> = Complexity::POLYNOMIAL
+        .iter()
+        .map(|&complexity| {
+            // ln t = ln c + ln f(n), so ln c is the mean difference.
+            let diffs: Vec<_> = points
+                .iter()
+                .map(|&(n, t)| t.ln() - complexity.ln_f(n))
+                .collect();
+            let ln_c = diffs.iter().sum::<f64>() / diffs.len() as f64;
+            let error = rms(diffs.iter().map(|d| d - ln_c).collect());
+            Fit { complexity, error }
+        })
+        .collect();
+
+    // ln t = ln c + n ln b, where the base b is fitted as well. The extra
+    // freedom only counts when it beats a free power law, which has as
+    // many parameters.
+    let residuals = |xs: &dyn Fn(f64) -> f64| {
+        let (slope, intercept) =
+            regression(points.iter().map(|&(n, t)| (xs(n), t.ln())));
+        rms(points
+            .iter()
+            .map(|&(n, t)| t.ln() - intercept - slope * xs(n))
+            .collect())
+    };
+    let exponential = residuals(&|n| n);
+    if exponential < residuals(&f64::ln) {
+        fits.push(Fit {
+            complexity: Complexity::Exponential,
+            error: exponential,
+        });
+    }
+
+    fits.sort_by(|a, b| a.error.total_cmp(&b.error));
+    fits
+}
+
+/// The slope and intercept of the least squares line through `points`.
+fn regression(points: impl Iterator<Item = (f64, f64)> + Clone) -> (f64, f64) {
+    let n = points.clone().count() as f64;
+    let (sx, sy) =
+        points.clone().fold((0.0, 0.0), |(sx, sy), (x, y)| (sx + x, sy + y));
+    let (mx, my) = (sx / n, sy / n);
+    let (sxy, sxx) = points.fold((0.0, 0.0), |(sxy, sxx), (x, y)| {
+        (sxy + (x - mx) * (y - my), sxx + (x - mx) * (x - mx))
+    });
+    let slope = if sxx == 0.0 { 0.0 } else { sxy / sxx };
+    (slope, my - slope * mx)
+}
@@ -0,0 +1,143 @@
+use std::{
+    error::Error,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+};
+
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use super::{params::ParamSpec, CliServe, Day};
+use crate::input;
+
+/// The days and their parameters, as listed by `GET /days`.
+pub type DayList = Vec<(u32, Vec<&'static dyn ParamSpec>)>;
+
+/// Serve the JSON API on localhost until the process is stopped:
+///
+/// - `GET /days` lists the days and their parameters.
+/// - `POST /days/{n}/parts/{p}` solves part `p` of day `n` for the input in
+///   the request body.
+///
+/// `with_input` makes day `n` read the given input, if there is such a day.
+pub fn run(
+    opts: &CliServe,
+    days: DayList,
+    with_input: impl Fn(u32, &Path) -> Option<Box<dyn Day>>,
+) -> Result<(), Box<dyn Error>> {
+    let server = Server::http(("127.0.0.1", opts.port))
+        .map_err(|e| format!("cannot listen on port {}: {}", opts.port, e))?;
+    println!("serving on http://127.0.0.1:{}", opts.port);
+
+    // Requests are served one at a time, so they can share the path their
+    // input is held under.
+    let path = PathBuf::from("<request body>");
+    for mut request in server.incoming_requests() {
+        let (status, body) =
+            match route(&mut request, &days, &path, &with_input) {
+                Ok(ok) => ok,
+                Err(e) => (500, json!({ "error": e.to_string() })),
+            };
+        let header =
+            Header::from_bytes("Content-Type", "application/json").unwrap();
+        let response = Response::from_string(body.to_string())
+            .with_status_code(status)
+            .with_header(header);
+        if let Err(e) = request.respond(response) {
+            eprintln!("failed to respond: {}", e);
+        }
+    }
+    Ok(())
+}
+
+fn route(
+    request: &mut Request,
+    days: &DayList,
+    path: &Path,
+    with_input: &impl Fn(u32, &Path) -> Option<Box<dyn Day>>,
+) -> Result<(u16, Value), Box<dyn Error>> {
+    let url = request.url().split('?').next().unwrap_or_default().to_string();
+    let segments: Vec<_> = url.split('/').filter(|s| !s.is_empty()).collect();
+
+    match (request.method(), segments.as_slice()) {
+        (Method::Get, ["days"]) => Ok((200, list(days))),
+        (Method::Post, ["days", n, "parts", p]) => {
+            let (n, part) = match (n.parse::<u32>(), p.parse::<u8>()) {
+                (Ok(n), Ok(part @ (1 | 2))) => (n, part),
+                _ => return Ok(error(400, "expected /days/{n}/parts/{1|2}")),
+            };
+            // Days only read their input once a part runs.
+            let day = match with_input(n, path) {
+                Some(day) => day,
+                None => return Ok(error(404, &format!("no such day: {}", n))),
+            };
+            let mut input = String::new();
+            if request.as_reader().read_to_string(&mut input).is_err() {
+                return Ok(error(400, "the input must be utf-8"));
+            }
+
+            let held = input::hold(path.to_path_buf(), input.into_bytes());
+            let solved = panic::catch_unwind(AssertUnwindSafe(|| match part {
+                1 => day.run_part1(),
+                _ => day.run_part2(),
+            }));
+            drop(held);
+
+            let timed = match solved {
+                Ok(timed) => timed?,
+                Err(_) => return Ok(error(500, "the solver panicked")),
+            };
+            let body = match timed.solution {
+                Ok(answer) => (
+                    200,
+                    json!({
+                        "day": n,
+                        "part": part,
+                        "answer": answer,
+                        "submission": answer.submission().ok(),
+                        "time_us": timed.time,
+                    }),
+                ),
+                Err(e) => (
+                    422,
+                    json!({
+                        "day": n,
+                        "part": part,
+                        "error": e.to_string(),
+                        "time_us": timed.time,
+                    }),
+                ),
+            };
+            Ok(body)
+        }
+        (_, ["days"] | ["days", _, "parts", _]) => {
+            Ok(error(405, "method not allowed"))
+        }
+        _ => Ok(error(404, &format!("not found: {}", url))),
+    }
+}
+
+fn list(days: &DayList) -> Value {
+    let days: Vec<_> = days
+        .iter()
+        .map(|(n, params)| {
+            let params: Vec<_> = params
+                .iter()
+                .map(|p| {
+                    json!({
+                        "name": p.name(),
+                        "type": p.type_name(),
+                        "default": p.default_value(),
+                        "help": p.help(),
+                    })
+                })
+                .collect();
+            json!({ "day": n, "parts": [1, 2], "params": params })
+        })
+        .collect();
+    json!({ "days": days })
+}
+
+fn error(status: u16, message: &str) -> (u16, Value) {
+    (status, json!({ "error": message }))
+}
@@ -0,0 +1,280 @@
+#[cfg(feature = "snapshots")]
+use std::sync::Mutex;
+use std::{error::Error, fs, path::PathBuf, sync::OnceLock};
+
+#[cfg(feature = "snapshots")]
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[cfg(feature = "snapshots")]
+use super::day_number;
+#[cfg(feature = "simulation")]
+use crate::simulation::Simulation;
+
+/// Bumped whenever the layout of [`SnapshotFile`] changes.
+const FORMAT_VERSION: u32 = 1;
+
+static OPTIONS: OnceLock<Options> = OnceLock::new();
+/// The snapshot file being resumed from.
+static RESUMED: OnceLock<SnapshotFile> = OnceLock::new();
+/// The snapshots taken so far, which are all written to the dump file.
+#[cfg(feature = "snapshots")]
+static TAKEN: Mutex<Vec<Entry>> = Mutex::new(Vec::new());
+
+/// The `--stop-at`, `--dump` and `--resume` options. Only the days that
+/// take snapshots read the first two.
+#[derive(Default)]
+#[cfg_attr(not(feature = "snapshots"), allow(dead_code))]
+pub struct Options {
+    pub stop_at: Option<usize>,
+    pub dump: Option<PathBuf>,
+    pub resume: Option<PathBuf>,
+}
+
+/// Read the snapshot file to resume from, if any, and make the options
+/// visible to [`Session`].
+pub fn init(options: Options) -> Result<(), Box<dyn Error>> {
+    if let Some(path) = &options.resume {
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+        let file: SnapshotFile = serde_json::from_str(&text).map_err(|e| {
+            format!("invalid snapshot {}: {}", path.display(), e)
+        })?;
+        if file.version != FORMAT_VERSION {
+            return Err(format!(
+                "snapshot {} has format version {}, not {}",
+                path.display(),
+                file.version,
+                FORMAT_VERSION
+            )
+            .into());
+        }
+        let _ = RESUMED.set(file);
+    }
+    let _ = OPTIONS.set(options);
+    Ok(())
+}
+
+/// A model whose state can be saved and restored, so that a simulation can
+/// be stopped and picked up again later.
+#[cfg(feature = "snapshots")]
+pub trait Snapshot {
+    type State: Serialize + DeserializeOwned;
+
+    fn save(&self) -> Self::State;
+
+    /// Replace the state of a model made from the same input.
+    fn restore(&mut self, state: Self::State) -> Result<(), Box<dyn Error>>;
+}
+
+/// A snapshot file holds the state of every part that was stopped or
+/// dumped during a run, as JSON:
+///
+/// ```json
+/// {
+///   "version": 1,
+///   "snapshots": [
+///     { "day": 11, "part": 2, "step": 195, "state": { ... } }
+///   ]
+/// }
+/// ```
+///
+/// `step` counts the steps taken so far; what a step is depends on the day:
+/// a step of the octopuses on day 11, a number drawn on day 4, a day of the
+/// lanternfish on day 6 and a fold on day 13. `state` is the day's
+/// [`Snapshot::State`]:
+///
+/// - day 4: `{ "marks": [["#....", ...], ...] }`, the marked spots of each
+///   board as rows of `#` and `.`.
+/// - day 6: `{ "timers": [n0, ..., n8] }`, the number of fish per timer.
+/// - day 11: `{ "energy": ["5483143223", ...], "flashes": 0 }`, the energy
+///   levels as rows of digits and the flashes so far.
+/// - day 13: `{ "dots": ["#.##.", ...] }`, the paper as rows of `#` and `.`.
+///
+/// The states only cover what changes, so a snapshot is resumed against the
+/// same input it was taken from.
+#[derive(Serialize, Deserialize)]
+struct SnapshotFile {
+    version: u32,
+    snapshots: Vec<Entry>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct Entry {
+    day: u32,
+    part: u8,
+    step: usize,
+    state: Value,
+}
+
+/// A part of a day stepping a model, which starts from a snapshot when one
+/// of the part is being resumed and stops at the `--stop-at` step.
+#[cfg(feature = "snapshots")]
+pub struct Session {
+    day: u32,
+    part: u8,
+    /// The step the model is at after it was restored.
+    pub start: usize,
+}
+
+#[cfg(feature = "snapshots")]
+impl Session {
+    /// Restore `model` if there is a snapshot of `part` of the day in
+    /// `module` to resume from.
+    pub fn start<M: Snapshot>(
+        module: &str,
+        part: u8,
+        model: &mut M,
+    ) -> Result<Self, Box<dyn Error>> {
+        let day = day_number(module);
+        let entry = RESUMED.get().and_then(|file| {
+            file.snapshots.iter().find(|e| e.day == day && e.part == part)
+        });
+        let start = match entry {
+            Some(entry) => {
+                let state = serde_json::from_value(entry.state.clone())
+                    .map_err(|e| {
+                        format!("invalid day {} snapshot: {}", day, e)
+                    })?;
+                model.restore(state)?;
+                entry.step
+            }
+            None => 0,
+        };
+        Ok(Self { day, part, start })
+    }
+
+    /// Call before taking the step after `step`: at the `--stop-at` step
+    /// the model is dumped and the part stops with an error.
+    pub fn pause<M: Snapshot>(
+        &self,
+        step: usize,
+        model: &M,
+    ) -> Result<(), Box<dyn Error>> {
+        let options = options();
+        if options.stop_at != Some(step) {
+            return Ok(());
+        }
+        match &options.dump {
+            Some(path) => {
+                self.dump(step, model)?;
+                Err(format!(
+                    "stopped at step {}, snapshot saved to {}",
+                    step,
+                    path.display()
+                )
+                .into())
+            }
+            None => Err(format!("stopped at step {}", step).into()),
+        }
+    }
+
+    /// Call once the part is done after `step` steps: without a
+    /// `--stop-at` step, or when it is this one, the model is dumped.
+    pub fn finish<M: Snapshot>(
+        &self,
+        step: usize,
+        model: &M,
+    ) -> Result<(), Box<dyn Error>> {
+        let options = options();
+        if options.stop_at.is_none_or(|s| s == step) {
+            self.dump(step, model)?;
+        }
+        Ok(())
+    }
+
+    /// Take steps until `steps` were taken in all, skipping ahead once the
+    /// model repeats itself. The `--stop-at` step is still reached exactly,
+    /// and only the state there or at the end is dumped.
+    #[cfg(feature = "simulation")]
+    pub fn run<M>(
+        &self,
+        model: &mut M,
+        steps: usize,
+    ) -> Result<(), Box<dyn Error>>
+    where
+        M: Simulation + Snapshot,
+    {
+        let end = self.stop_before(steps)?;
+        model.advance(end - self.start);
+        if end < steps {
+            self.pause(end, model)?;
+        }
+        self.finish(steps, model)
+    }
+
+    /// Step until `done` accepts the state and output after a step,
+    /// returning how many steps were taken in all.
+    #[cfg(feature = "simulation-until")]
+    pub fn run_until<M>(
+        &self,
+        model: &mut M,
+        mut done: impl FnMut(&M, &M::Output) -> bool,
+    ) -> Result<usize, Box<dyn Error>>
+    where
+        M: Simulation + Snapshot,
+    {
+        self.pause(self.start, model)?;
+        let mut step = self.start;
+        let mut paused = Ok(());
+        model.run_until(|model, output| {
+            step += 1;
+            if done(model, output) {
+                return true;
+            }
+            paused = self.pause(step, model);
+            paused.is_err()
+        });
+        paused?;
+        self.finish(step, model)?;
+        Ok(step)
+    }
+
+    /// The step to stop at on the way to `steps`.
+    #[cfg(feature = "simulation")]
+    fn stop_before(&self, steps: usize) -> Result<usize, Box<dyn Error>> {
+        if self.start > steps {
+            return Err(format!(
+                "the snapshot is at step {}, past the last step {}",
+                self.start, steps
+            )
+            .into());
+        }
+        Ok(match options().stop_at {
+            Some(stop) if (self.start..steps).contains(&stop) => stop,
+            _ => steps,
+        })
+    }
+
+    fn dump<M: Snapshot>(
+        &self,
+        step: usize,
+        model: &M,
+    ) -> Result<(), Box<dyn Error>> {
+        let path = match &options().dump {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+        let entry = Entry {
+            day: self.day,
+            part: self.part,
+            step,
+            state: serde_json::to_value(model.save())?,
+        };
+
+        let mut taken = TAKEN.lock().unwrap();
+        taken.retain(|e| (e.day, e.part) != (self.day, self.part));
+        taken.push(entry);
+        let file =
+            SnapshotFile { version: FORMAT_VERSION, snapshots: taken.clone() };
+        fs::write(path, serde_json::to_string_pretty(&file)? + "\n")
+            .map_err(|e| format!("cannot write {}: {}", path.display(), e))?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "snapshots")]
+fn options() -> &'static Options {
+    OPTIONS.get_or_init(Options::default)
+}
@@ -0,0 +1,551 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    error::Error,
+    hash::Hash,
+    ops::Add,
+};
+
+/// An adjacency-list graph whose nodes are interned: each distinct node value
+/// gets an index, and every query and algorithm works in terms of indexes.
+pub struct Graph<N, W = ()> {
+    nodes: Vec<N>,
+    indexes: HashMap<N, usize>,
+    adj: Vec<Vec<(usize, W)>>,
+    directed: bool,
+}
+
+impl<N, W> Graph<N, W>
+where
+    N: Clone + Eq + Hash,
+{
+    pub fn directed() -> Self {
+        Self::new(true)
+    }
+
+    pub fn undirected() -> Self {
+        Self::new(false)
+    }
+
+    fn new(directed: bool) -> Self {
+        Self {
+            nodes: Vec::new(),
+            indexes: HashMap::new(),
+            adj: Vec::new(),
+            directed,
+        }
+    }
+
+    /// The index of `node`, adding it to the graph if it is new.
+    pub fn add_node(&mut self, node: N) -> usize {
+        if let Some(&i) = self.indexes.get(&node) {
+            return i;
+        }
+        let i = self.nodes.len();
+        self.indexes.insert(node.clone(), i);
+        self.nodes.push(node);
+        self.adj.push(Vec::new());
+        i
+    }
+
+    pub fn add_weighted_edge(&mut self, a: N, b: N, weight: W) -> (usize, usize)
+    where
+        W: Clone,
+    {
+        let (a, b) = (self.add_node(a), self.add_node(b));
+        if !self.directed {
+            self.adj[b].push((a, weight.clone()));
+        }
+        self.adj[a].push((b, weight));
+        (a, b)
+    }
+
+    pub fn index_of(&self, node: &N) -> Option<usize> {
+        self.indexes.get(node).copied()
+    }
+}
+
+impl<N> Graph<N, ()>
+where
+    N: Clone + Eq + Hash,
+{
+    pub fn add_edge(&mut self, a: N, b: N) -> (usize, usize) {
+        self.add_weighted_edge(a, b, ())
+    }
+}
+
+impl<N, W> Graph<N, W> {
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    #[inline]
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    #[inline]
+    pub fn node(&self, i: usize) -> &N {
+        &self.nodes[i]
+    }
+
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+
+    /// The outgoing edges of node `i` as `(neighbor, weight)` pairs.
+    #[inline]
+    pub fn edges(&self, i: usize) -> &[(usize, W)] {
+        &self.adj[i]
+    }
+
+    #[inline]
+    pub fn neighbors(&self, i: usize) -> impl Iterator<Item = usize> + '_ {
+        self.adj[i].iter().map(|(n, _)| *n)
+    }
+
+    /// The number of edges on the shortest path from `start` to each node,
+    /// or `None` for unreachable nodes.
+    pub fn bfs(&self, start: usize) -> Vec<Option<usize>> {
+        let mut dist = vec![None; self.len()];
+        let mut queue = VecDeque::from([start]);
+        dist[start] = Some(0);
+        while let Some(node) = queue.pop_front() {
+            let d = dist[node].map(|d| d + 1);
+            for n in self.neighbors(node) {
+                if dist[n].is_none() {
+                    dist[n] = d;
+                    queue.push_back(n);
+                }
+            }
+        }
+        dist
+    }
+
+    /// The nodes reachable from `start`, in depth-first preorder.
+    pub fn dfs(&self, start: usize) -> Vec<usize> {
+        let mut seen = vec![false; self.len()];
+        let mut order = Vec::new();
+        let mut stack = vec![start];
+        while let Some(node) = stack.pop() {
+            if seen[node] {
+                continue;
+            }
+            seen[node] = true;
+            order.push(node);
+            stack.extend(self.adj[node].iter().rev().map(|(n, _)| *n));
+        }
+        order
+    }
+
+    /// The number of walks from `start` that reach `goal`, explored depth
+    /// first. A walk only steps onto the nodes `enter` accepts and ends at
+    /// `goal`; `leave` is called as it steps back off a node it entered, so
+    /// that `state` can track what the current walk has visited.
+    pub fn count_walks<S>(
+        &self,
+        start: usize,
+        goal: usize,
+        state: &mut S,
+        enter: impl Copy + Fn(&mut S, usize) -> bool,
+        leave: impl Copy + Fn(&mut S, usize),
+    ) -> usize {
+        let mut count = 0;
+        for n in self.neighbors(start) {
+            if !enter(state, n) {
+                continue;
+            }
+            if n == goal {
+                count += 1;
+            } else {
+                count += self.count_walks(n, goal, state, enter, leave);
+            }
+            leave(state, n);
+        }
+        count
+    }
+
+    /// The order in which every edge points forwards, or an error if the
+    /// graph has a cycle.
+    pub fn topological_sort(&self) -> Result<Vec<usize>, Box<dyn Error>> {
+        if !self.directed {
+            return Err("cannot sort an undirected graph".into());
+        }
+
+        let mut in_degree = vec![0_usize; self.len()];
+        for edges in &self.adj {
+            for (n, _) in edges {
+                in_degree[*n] += 1;
+            }
+        }
+
+        let mut queue: VecDeque<usize> =
+            (0..self.len()).filter(|&i| in_degree[i] == 0).collect();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+            for n in self.neighbors(node) {
+                in_degree[n] -= 1;
+                if in_degree[n] == 0 {
+                    queue.push_back(n);
+                }
+            }
+        }
+
+        if order.len() == self.len() {
+            Ok(order)
+        } else {
+            Err("graph has a cycle".into())
+        }
+    }
+
+    /// Tarjan's algorithm. Components are listed in reverse topological
+    /// order of the condensed graph.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<usize>> {
+        const UNVISITED: usize = usize::MAX;
+
+        let mut index = vec![UNVISITED; self.len()];
+        let mut low_link = vec![0; self.len()];
+        let mut on_stack = vec![false; self.len()];
+        let mut stack = Vec::new();
+        let mut next_index = 0;
+        let mut components = Vec::new();
+
+        for root in 0..self.len() {
+            if index[root] != UNVISITED {
+                continue;
+            }
+
+            // (node, next edge to explore)
+            let mut work = vec![(root, 0)];
+            while let Some(&mut (node, ref mut edge_i)) = work.last_mut() {
+                if *edge_i == 0 {
+                    index[node] = next_index;
+                    low_link[node] = next_index;
+                    next_index += 1;
+                    stack.push(node);
+                    on_stack[node] = true;
+                }
+
+                if let Some(&(n, _)) = self.adj[node].get(*edge_i) {
+                    *edge_i += 1;
+                    if index[n] == UNVISITED {
+                        work.push((n, 0));
+                    } else if on_stack[n] {
+                        low_link[node] = low_link[node].min(index[n]);
+                    }
+                    continue;
+                }
+
+                work.pop();
+                if let Some(&(parent, _)) = work.last() {
+                    low_link[parent] = low_link[parent].min(low_link[node]);
+                }
+
+                if low_link[node] == index[node] {
+                    let mut component = Vec::new();
+                    while let Some(n) = stack.pop() {
+                        on_stack[n] = false;
+                        component.push(n);
+                        if n == node {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+            }
+        }
+
+        components
+    }
+
+    /// The sets of nodes connected to each other when edge direction is
+    /// ignored.
+    pub fn connected_components(&self) -> Vec<Vec<usize>> {
+        let mut parent: Vec<usize> = (0..self.len()).collect();
+
+        fn find(parent: &mut [usize], mut i: usize) -> usize {
+            while parent[i] != i {
+                parent[i] = parent[parent[i]];
+                i = parent[i];
+            }
+            i
+        }
+
+        for (a, edges) in self.adj.iter().enumerate() {
+            for (b, _) in edges {
+                let (ra, rb) = (find(&mut parent, a), find(&mut parent, *b));
+                if ra != rb {
+                    parent[ra.max(rb)] = ra.min(rb);
+                }
+            }
+        }
+
+        let mut components: Vec<Vec<usize>> = Vec::new();
+        let mut component_of = HashMap::new();
+        for i in 0..self.len() {
+            let root = find(&mut parent, i);
+            let c = *component_of.entry(root).or_insert_with(|| {
+                components.push(Vec::new());
+                components.len() - 1
+            });
+            components[c].push(i);
+        }
+        components
+    }
+}
+
+impl<N, W> Graph<N, W>
+where
+    W: Copy + Ord + Default + Add<Output = W>,
+{
+    /// The total weight of the lightest path from `start` to each node, or
+    /// `None` for unreachable nodes. Weights must not be negative.
+    pub fn dijkstra(&self, start: usize) -> Vec<Option<W>> {
+        let mut dist = vec![None; self.len()];
+        let mut heap = BinaryHeap::from([Reverse((W::default(), start))]);
+        while let Some(Reverse((d, node))) = heap.pop() {
+            if dist[node].is_some() {
+                continue;
+            }
+            dist[node] = Some(d);
+            for &(n, w) in &self.adj[node] {
+                if dist[n].is_none() {
+                    heap.push(Reverse((d + w, n)));
+                }
+            }
+        }
+        dist
+    }
+
+    /// The lightest path from `start` to `goal` and its weight. `heuristic`
+    /// must never overestimate the remaining weight to `goal`.
+    pub fn astar(
+        &self,
+        start: usize,
+        goal: usize,
+        heuristic: impl Fn(usize) -> W,
+    ) -> Option<(W, Vec<usize>)> {
+        let mut best: Vec<Option<W>> = vec![None; self.len()];
+        let mut came_from = vec![usize::MAX; self.len()];
+        let mut heap = BinaryHeap::from([Reverse((
+            heuristic(start),
+            W::default(),
+            start,
+        ))]);
+        best[start] = Some(W::default());
+
+        while let Some(Reverse((_, d, node))) = heap.pop() {
+            // A lighter path to `node` was found after this entry was pushed.
+            if best[node].is_some_and(|b| d > b) {
+                continue;
+            }
+            if node == goal {
+                let mut path = vec![goal];
+                while let Some(&prev) = path.last().map(|&n| &came_from[n]) {
+                    if prev == usize::MAX {
+                        break;
+                    }
+                    path.push(prev);
+                }
+                path.reverse();
+                return Some((d, path));
+            }
+            for &(n, w) in &self.adj[node] {
+                let nd = d + w;
+                if best[n].is_none_or(|b| nd < b) {
+                    best[n] = Some(nd);
+                    came_from[n] = node;
+                    heap.push(Reverse((nd + heuristic(n), nd, n)));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// a - b - c   d, with `d` on its own.
+    fn line() -> Graph<char> {
+        let mut graph = Graph::undirected();
+        graph.add_edge('a', 'b');
+        graph.add_edge('b', 'c');
+        graph.add_node('d');
+        graph
+    }
+
+    fn sorted(mut components: Vec<Vec<usize>>) -> Vec<Vec<usize>> {
+        for c in &mut components {
+            c.sort();
+        }
+        components.sort();
+        components
+    }
+
+    #[test]
+    fn nodes_are_interned() {
+        let mut graph = line();
+        assert_eq!(graph.len(), 4);
+        assert_eq!(graph.add_node('b'), 1);
+        assert_eq!(graph.index_of(&'c'), Some(2));
+        assert_eq!(graph.index_of(&'z'), None);
+        assert_eq!(*graph.node(3), 'd');
+        assert_eq!(graph.neighbors(1).collect::<Vec<_>>(), [0, 2]);
+    }
+
+    #[test]
+    fn bfs_counts_edges() {
+        assert_eq!(line().bfs(0), [Some(0), Some(1), Some(2), None]);
+    }
+
+    #[test]
+    fn dfs_visits_in_preorder() {
+        let mut graph = Graph::directed();
+        graph.add_edge(0, 1);
+        graph.add_edge(0, 2);
+        graph.add_edge(1, 3);
+        graph.add_edge(3, 0);
+        assert_eq!(graph.dfs(0), [0, 1, 3, 2]);
+        assert_eq!(graph.dfs(2), [2]);
+    }
+
+    #[test]
+    fn count_walks_follows_enter_and_leave() {
+        // Every simple path from a to d in a square with a diagonal.
+        let mut graph = Graph::undirected();
+        for (a, b) in [('a', 'b'), ('b', 'd'), ('a', 'c'), ('c', 'd')] {
+            graph.add_edge(a, b);
+        }
+        graph.add_edge('b', 'c');
+        let (a, d) =
+            (graph.index_of(&'a').unwrap(), graph.index_of(&'d').unwrap());
+
+        let mut seen = vec![false; graph.len()];
+        seen[a] = true;
+        let enter = |seen: &mut Vec<bool>, n: usize| {
+            !std::mem::replace(&mut seen[n], true)
+        };
+        let leave = |seen: &mut Vec<bool>, n: usize| seen[n] = false;
+        assert_eq!(graph.count_walks(a, d, &mut seen, enter, leave), 4);
+        assert_eq!(seen, [true, false, false, false]);
+    }
+
+    #[test]
+    fn topological_sort_orders_edges_forwards() {
+        let mut graph = Graph::directed();
+        graph.add_edge("shirt", "tie");
+        graph.add_edge("tie", "jacket");
+        graph.add_edge("trousers", "shoes");
+        graph.add_edge("trousers", "jacket");
+        let order = graph.topological_sort().unwrap();
+        let position = |node| {
+            let i = graph.index_of(&node).unwrap();
+            order.iter().position(|&n| n == i).unwrap()
+        };
+        assert!(position("shirt") < position("tie"));
+        assert!(position("tie") < position("jacket"));
+        assert!(position("trousers") < position("shoes"));
+        assert!(position("trousers") < position("jacket"));
+    }
+
+    #[test]
+    fn topological_sort_rejects_cycles() {
+        let mut graph = Graph::directed();
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 0);
+        assert!(graph.topological_sort().is_err());
+        assert!(line().topological_sort().is_err());
+    }
+
+    #[test]
+    fn strongly_connected_components() {
+        // 0 <-> 1 -> 2 <-> 3, and 4 alone.
+        let mut graph = Graph::directed();
+        for (a, b) in [(0, 1), (1, 0), (1, 2), (2, 3), (3, 2)] {
+            graph.add_edge(a, b);
+        }
+        graph.add_node(4);
+        let components = graph.strongly_connected_components();
+        assert_eq!(
+            sorted(components.clone()),
+            [vec![0, 1], vec![2, 3], vec![4]]
+        );
+        // The component reached from the other comes first.
+        let first = components.iter().position(|c| c.contains(&2));
+        let second = components.iter().position(|c| c.contains(&0));
+        assert!(first < second);
+    }
+
+    #[test]
+    fn connected_components_ignore_direction() {
+        let mut graph = Graph::directed();
+        graph.add_edge(0, 1);
+        graph.add_edge(2, 1);
+        graph.add_node(3);
+        assert_eq!(
+            sorted(graph.connected_components()),
+            [vec![0, 1, 2], vec![3]]
+        );
+        assert_eq!(
+            sorted(line().connected_components()),
+            [vec![0, 1, 2], vec![3]]
+        );
+    }
+
+    /// s -1- a -1- b -1- g, and a direct s -5- g.
+    fn weighted() -> Graph<char, u32> {
+        let mut graph = Graph::undirected();
+        graph.add_weighted_edge('s', 'a', 1);
+        graph.add_weighted_edge('a', 'b', 1);
+        graph.add_weighted_edge('b', 'g', 1);
+        graph.add_weighted_edge('s', 'g', 5);
+        graph.add_node('x');
+        graph
+    }
+
+    #[test]
+    fn dijkstra_finds_the_lightest_paths() {
+        assert_eq!(
+            weighted().dijkstra(0),
+            [Some(0), Some(1), Some(2), Some(3), None]
+        );
+    }
+
+    #[test]
+    fn astar_finds_the_lightest_path() {
+        let graph = weighted();
+        let goal = graph.index_of(&'g').unwrap();
+        assert_eq!(graph.astar(0, goal, |_| 0), Some((3, vec![0, 1, 2, goal])));
+        assert_eq!(graph.astar(0, 4, |_| 0), None);
+    }
+
+    #[test]
+    fn astar_skips_stale_entries() {
+        // b is first reached through the heavy edge, then through a; the
+        // stale entry for b must not be expanded with its old weight.
+        let mut graph = Graph::directed();
+        graph.add_weighted_edge('s', 'b', 10);
+        graph.add_weighted_edge('s', 'a', 1);
+        graph.add_weighted_edge('a', 'b', 1);
+        graph.add_weighted_edge('b', 'g', 1);
+        let goal = graph.index_of(&'g').unwrap();
+        let path: Vec<char> = graph
+            .astar(0, goal, |_| 0)
+            .map(|(w, path)| {
+                assert_eq!(w, 3);
+                path.iter().map(|&n| *graph.node(n)).collect()
+            })
+            .unwrap();
+        assert_eq!(path, ['s', 'a', 'b', 'g']);
+    }
+}
@@ -0,0 +1,355 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Write},
+    ops::{Index, IndexMut},
+};
+
+use crate::parse::{self, ParseError};
+
+const OFFSETS_4: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+const OFFSETS_8: [(isize, isize); 8] =
+    [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)];
+
+/// A rectangular grid stored row by row and indexed by `(x, y)`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Self { cells: vec![value; width * height], width, height }
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn from_fn(
+        width: usize,
+        height: usize,
+        mut f: impl FnMut(usize, usize) -> T,
+    ) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| f(x, y))
+            .collect();
+        Self { cells, width, height }
+    }
+
+    /// A grid `width` cells wide of `cells` given row by row.
+    pub fn from_cells(
+        width: usize,
+        cells: Vec<T>,
+    ) -> Result<Self, Box<dyn Error>> {
+        let height = cells.len().checked_div(width).unwrap_or(0);
+        if width * height != cells.len() {
+            return Err(format!(
+                "{} cells do not make rows of {}",
+                cells.len(),
+                width
+            )
+            .into());
+        }
+        Ok(Self { cells, width, height })
+    }
+
+    /// Parse one cell per character, one row per line.
+    pub fn parse_chars<E: Display>(
+        text: &str,
+        mut f: impl FnMut(char) -> Result<T, E>,
+    ) -> Result<Self, ParseError> {
+        let mut cells = Vec::with_capacity(text.len());
+        let mut width = None;
+        let mut height = 0;
+        for line in parse::lines(text) {
+            let start = cells.len();
+            line.try_for_each_char(|c| f(c).map(|cell| cells.push(cell)))?;
+            let len = cells.len() - start;
+            let expected = *width.get_or_insert(len);
+            if len != expected {
+                return Err(line.error(format!(
+                    "row has {} cells, expected {}",
+                    len, expected
+                )));
+            }
+            height += 1;
+        }
+        Ok(Self { cells, width: width.unwrap_or(0), height })
+    }
+
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    #[inline]
+    pub fn contains(&self, (x, y): (usize, usize)) -> bool {
+        x < self.width && y < self.height
+    }
+
+    #[inline]
+    pub fn get(&self, pos: (usize, usize)) -> Option<&T> {
+        if self.contains(pos) {
+            Some(&self.cells[pos.1 * self.width + pos.0])
+        } else {
+            None
+        }
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1)).take(self.height)
+    }
+
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> {
+        self.cells.chunks_mut(self.width.max(1)).take(self.height)
+    }
+
+    /// The cells of the rows above `y` and of the rows from `y` on.
+    pub fn split_at_row_mut(&mut self, y: usize) -> (&mut [T], &mut [T]) {
+        self.cells.split_at_mut(y * self.width)
+    }
+
+    /// Keep only the rows above `height`.
+    pub fn truncate_rows(&mut self, height: usize) {
+        if height < self.height {
+            self.cells.truncate(height * self.width);
+            self.height = height;
+        }
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} out of bounds", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    /// Every position in the grid, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// The orthogonally adjacent positions that are inside the grid.
+    pub fn neighbors4(
+        &self,
+        pos: (usize, usize),
+    ) -> impl Iterator<Item = (usize, usize)> {
+        self.neighbors_with(pos, &OFFSETS_4)
+    }
+
+    /// The orthogonally and diagonally adjacent positions that are inside
+    /// the grid.
+    pub fn neighbors8(
+        &self,
+        pos: (usize, usize),
+    ) -> impl Iterator<Item = (usize, usize)> {
+        self.neighbors_with(pos, &OFFSETS_8)
+    }
+
+    fn neighbors_with(
+        &self,
+        (x, y): (usize, usize),
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.width, self.height);
+        offsets.iter().filter_map(move |&(dx, dy)| {
+            let nx = x.checked_add_signed(dx)?;
+            let ny = y.checked_add_signed(dy)?;
+            (nx < width && ny < height).then_some((nx, ny))
+        })
+    }
+
+    /// Keep only the columns left of `width`.
+    pub fn truncate_columns(&mut self, width: usize)
+    where
+        T: Copy,
+    {
+        if width >= self.width {
+            return;
+        }
+        for y in 0..self.height {
+            let start = y * self.width;
+            self.cells.copy_within(start..start + width, y * width);
+        }
+        self.cells.truncate(self.height * width);
+        self.width = width;
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T: From<u8>> Grid<T> {
+    /// Parse a map of single decimal digits, one row per line.
+    pub fn parse_digits(text: &str) -> Result<Self, ParseError> {
+        Self::parse_chars(text, |c| match c.to_digit(10) {
+            Some(d) => Ok(T::from(d as u8)),
+            None => Err(format!("invalid digit: {:?}", c)),
+        })
+    }
+}
+
+// Indexing checks the column itself; the row is checked by the indexing of
+// `cells`, which is all a `get` followed by an unwrap would check twice.
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        if x >= self.width {
+            out_of_bounds((x, y), self.width, self.height);
+        }
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    #[inline]
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        if x >= self.width {
+            out_of_bounds((x, y), self.width, self.height);
+        }
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+#[cold]
+fn out_of_bounds(pos: (usize, usize), width: usize, height: usize) -> ! {
+    panic!("position {:?} out of bounds of {}x{} grid", pos, width, height)
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                f.write_char('\n')?;
+            }
+            for cell in row {
+                cell.fmt(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(mut positions: Vec<(usize, usize)>) -> Vec<(usize, usize)> {
+        positions.sort();
+        positions
+    }
+
+    #[test]
+    fn neighbors_stay_inside_at_the_edges() {
+        let grid = Grid::new(3, 2, 0);
+        assert_eq!(sorted(grid.neighbors4((0, 0)).collect()), [(0, 1), (1, 0)]);
+        assert_eq!(sorted(grid.neighbors4((2, 1)).collect()), [(1, 1), (2, 0)]);
+        assert_eq!(
+            sorted(grid.neighbors4((1, 0)).collect()),
+            [(0, 0), (1, 1), (2, 0)]
+        );
+        assert_eq!(
+            sorted(grid.neighbors8((0, 0)).collect()),
+            [(0, 1), (1, 0), (1, 1)]
+        );
+        assert_eq!(grid.neighbors8((1, 1)).count(), 5);
+    }
+
+    #[test]
+    fn neighbors_inside() {
+        let grid = Grid::new(3, 3, 0);
+        assert_eq!(grid.neighbors4((1, 1)).count(), 4);
+        assert_eq!(grid.neighbors8((1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn parse_chars_rejects_ragged_rows() {
+        let err = Grid::<u8>::parse_digits("123\n45\n678").unwrap_err();
+        assert_eq!(err.to_string(), "line 2: row has 2 cells, expected 3");
+
+        let err = Grid::<u8>::parse_digits("12\n345").unwrap_err();
+        assert_eq!(err.to_string(), "line 2: row has 3 cells, expected 2");
+    }
+
+    #[test]
+    fn parse_chars_points_at_the_invalid_char() {
+        let err = Grid::<u8>::parse_digits("123\n4x6").unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 2: invalid digit: 'x'");
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = Grid::<u8>::parse_digits("123\n456").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.rows().collect::<Vec<_>>(), [[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(grid.row(1), [4, 5, 6]);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), [2, 5]);
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(
+            grid.positions().collect::<Vec<_>>(),
+            [(0, 0), (1, 0), (2, 0), (0, 1), (1, 1), (2, 1)]
+        );
+    }
+
+    #[test]
+    fn from_cells_needs_whole_rows() {
+        let grid = Grid::from_cells(2, vec![1, 2, 3, 4]).unwrap();
+        assert_eq!(grid.to_string(), "12\n34");
+        assert!(Grid::from_cells(2, vec![1, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn truncate_rows_and_columns() {
+        let mut grid = Grid::<u8>::parse_digits("123\n456\n789").unwrap();
+        grid.truncate_columns(2);
+        assert_eq!(grid.to_string(), "12\n45\n78");
+        grid.truncate_rows(1);
+        assert_eq!(grid.to_string(), "12");
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn index_past_the_width_panics() {
+        let grid = Grid::new(2, 2, 0);
+        let _ = grid[(2, 0)];
+    }
+}
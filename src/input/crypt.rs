@@ -0,0 +1,116 @@
+use std::{
+    collections::HashMap,
+    env,
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use argon2::Argon2;
+use chacha20poly1305::{aead::Aead, Key, KeyInit, XChaCha20Poly1305, XNonce};
+
+pub const KEY_VAR: &str = "AOC_INPUT_KEY";
+pub const KEY_FILE_VAR: &str = "AOC_INPUT_KEY_FILE";
+const DEFAULT_KEY_FILE: &str = ".input-key";
+
+const MAGIC: &[u8; 8] = b"AOCENC02";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+
+type Salt = [u8; SALT_LEN];
+
+/// The secret the inputs are encrypted with. Each file stores a random salt
+/// its key was derived with, and the derived keys are kept so that a file
+/// read again in the same run is not derived again.
+pub struct InputKey {
+    secret: String,
+    keys: Mutex<HashMap<Salt, Key>>,
+}
+
+impl InputKey {
+    /// Load the key from `$AOC_INPUT_KEY`, or else from the file at
+    /// `$AOC_INPUT_KEY_FILE`, or else from `.input-key` in the crate root.
+    pub fn load() -> Result<Self, Box<dyn Error>> {
+        let secret = match env::var(KEY_VAR) {
+            Ok(secret) => secret,
+            Err(_) => {
+                let path = env::var_os(KEY_FILE_VAR)
+                    .map(PathBuf::from)
+                    .unwrap_or_else(|| {
+                        Path::new(env!("CARGO_MANIFEST_DIR"))
+                            .join(DEFAULT_KEY_FILE)
+                    });
+                fs::read_to_string(&path).map_err(|e| {
+                    format!(
+                        "no input key: set {} or {}, or create {}: {}",
+                        KEY_VAR,
+                        KEY_FILE_VAR,
+                        path.display(),
+                        e
+                    )
+                })?
+            }
+        };
+
+        let secret = secret.trim();
+        if secret.is_empty() {
+            return Err("input key is empty".into());
+        }
+
+        Ok(Self {
+            secret: secret.to_string(),
+            keys: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// The key derived from the secret with Argon2id and `salt`.
+    fn derive(&self, salt: &Salt) -> Result<Key, Box<dyn Error>> {
+        let mut keys = self.keys.lock().unwrap();
+        if let Some(key) = keys.get(salt) {
+            return Ok(*key);
+        }
+        let mut key = Key::default();
+        Argon2::default()
+            .hash_password_into(self.secret.as_bytes(), salt, &mut key)
+            .map_err(|e| format!("failed to derive the input key: {}", e))?;
+        keys.insert(*salt, key);
+        Ok(key)
+    }
+
+    pub fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+        let salt: Salt = rand::random();
+        let key = self.derive(&salt)?;
+        let mut nonce = XNonce::default();
+        nonce.copy_from_slice(&rand::random::<[u8; NONCE_LEN]>());
+
+        let ciphertext = XChaCha20Poly1305::new(&key)
+            .encrypt(&nonce, plaintext)
+            .map_err(|_| "failed to encrypt input")?;
+
+        let mut data = Vec::with_capacity(
+            MAGIC.len() + SALT_LEN + NONCE_LEN + ciphertext.len(),
+        );
+        data.extend_from_slice(MAGIC);
+        data.extend_from_slice(&salt);
+        data.extend_from_slice(&nonce);
+        data.extend_from_slice(&ciphertext);
+        Ok(data)
+    }
+
+    pub fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+        let data = match data.strip_prefix(MAGIC) {
+            Some(data) if data.len() >= SALT_LEN + NONCE_LEN => data,
+            _ => return Err("not an encrypted input".into()),
+        };
+        let (salt, data) = data.split_at(SALT_LEN);
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+        let mut nonce_arr = XNonce::default();
+        nonce_arr.copy_from_slice(nonce);
+
+        let key = self.derive(salt.try_into().unwrap())?;
+        XChaCha20Poly1305::new(&key)
+            .decrypt(&nonce_arr, ciphertext)
+            .map_err(|_| "wrong input key or corrupted input".into())
+    }
+}
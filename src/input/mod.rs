@@ -0,0 +1,219 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    ffi::OsString,
+    fs::{self, File},
+    ops::Deref,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard, OnceLock},
+};
+
+use memmap2::Mmap;
+use tracing::{debug, debug_span};
+
+mod crypt;
+use self::crypt::InputKey;
+use crate::config;
+
+/// Path of the encrypted counterpart of an input, e.g. `input.txt.enc`.
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut enc_path = OsString::from(path);
+    enc_path.push(".enc");
+    PathBuf::from(enc_path)
+}
+
+/// The input key, loaded once so that its derived keys are shared by all
+/// the inputs of a run.
+fn key() -> Result<&'static InputKey, Box<dyn Error>> {
+    static KEY: OnceLock<InputKey> = OnceLock::new();
+    if let Some(key) = KEY.get() {
+        return Ok(key);
+    }
+    let key = InputKey::load()?;
+    Ok(KEY.get_or_init(|| key))
+}
+
+/// Locate a day's input under the configured inputs root, trying
+/// `dayNN/input.txt` and then `NN.txt`, or else use `fallback`.
+pub fn resolve(day: u32, fallback: PathBuf) -> PathBuf {
+    if let Some(root) = &config::get().inputs_dir {
+        let candidates = [
+            root.join(format!("day{:02}", day)).join("input.txt"),
+            root.join(format!("{:02}.txt", day)),
+        ];
+        for path in candidates {
+            if path.exists() || encrypted_path(&path).exists() {
+                return path;
+            }
+        }
+    }
+    fallback
+}
+
+/// The contents of an input file. Plain files are memory-mapped and inputs
+/// held in memory are shared rather than copied, so days can parse borrowed
+/// slices of it directly.
+pub struct Input {
+    /// Only named in the errors of [`Input::text`], which days and plugins
+    /// use.
+    #[cfg_attr(
+        not(any(feature = "any-day", feature = "plugins")),
+        allow(dead_code)
+    )]
+    path: PathBuf,
+    data: Data,
+}
+
+enum Data {
+    Mapped(Mmap),
+    Shared(Arc<[u8]>),
+}
+
+impl Input {
+    #[cfg(any(feature = "any-day", feature = "plugins"))]
+    pub fn text(&self) -> Result<&str, Box<dyn Error>> {
+        std::str::from_utf8(self).map_err(|e| {
+            format!("input is not utf-8: {}: {}", self.path.display(), e).into()
+        })
+    }
+}
+
+impl Deref for Input {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match &self.data {
+            Data::Mapped(map) => map,
+            Data::Shared(bytes) => bytes,
+        }
+    }
+}
+
+/// Inputs kept in memory by the path days are given for them, which they
+/// load instead of a file.
+fn held() -> MutexGuard<'static, HashMap<PathBuf, Arc<[u8]>>> {
+    static HELD: OnceLock<Mutex<HashMap<PathBuf, Arc<[u8]>>>> = OnceLock::new();
+    HELD.get_or_init(Default::default).lock().unwrap()
+}
+
+/// Keeps an input in memory while it lives.
+#[cfg(any(feature = "serve", feature = "ffi"))]
+pub struct Held(PathBuf);
+
+#[cfg(any(feature = "serve", feature = "ffi"))]
+impl Drop for Held {
+    fn drop(&mut self) {
+        held().remove(&self.0);
+    }
+}
+
+/// Make `data` the input at `path` until the returned guard is dropped, so
+/// that a day can solve a buffer without writing it to a file.
+#[cfg(any(feature = "serve", feature = "ffi"))]
+pub fn hold(path: PathBuf, data: Vec<u8>) -> Held {
+    held().insert(path.clone(), data.into());
+    Held(path)
+}
+
+/// Map a plain input. Anything but a regular file, such as a pipe or
+/// `/dev/stdin`, can only be read once, so its contents are held for the
+/// other parts of the run.
+fn read(path: &Path) -> Result<Data, Box<dyn Error>> {
+    let file = File::open(path)?;
+    let metadata = file.metadata()?;
+    if metadata.is_file() && metadata.len() > 0 {
+        // SAFETY: inputs are not modified while a day runs; if one were
+        // truncated underneath us we would read garbage or fault, which
+        // is acceptable for a puzzle runner.
+        return Ok(Data::Mapped(unsafe { Mmap::map(&file)? }));
+    }
+    let data: Arc<[u8]> = fs::read(path)?.into();
+    if !metadata.is_file() {
+        held().insert(path.to_path_buf(), Arc::clone(&data));
+    }
+    Ok(Data::Shared(data))
+}
+
+/// Load an input file, falling back to decrypting its `.enc` counterpart
+/// when the plaintext file does not exist.
+pub fn load(path: impl AsRef<Path>) -> Result<Input, Box<dyn Error>> {
+    let path = path.as_ref().to_path_buf();
+    let _span = debug_span!("read", path = %path.display()).entered();
+    if let Some(data) = held().get(&path) {
+        return Ok(Input { data: Data::Shared(Arc::clone(data)), path });
+    }
+    if path.exists() {
+        let data = read(&path)?;
+        return Ok(Input { path, data });
+    }
+
+    let enc_path = encrypted_path(&path);
+    if enc_path.exists() {
+        debug!(path = %enc_path.display(), "decrypting");
+        let data = fs::read(&enc_path)?;
+        let plaintext = key()?.decrypt(&data).map_err(|e| {
+            format!("failed to decrypt {}: {}", enc_path.display(), e)
+        })?;
+        return Ok(Input { path, data: Data::Shared(plaintext.into()) });
+    }
+
+    Err(format!("input not found: {}", path.display()).into())
+}
+
+pub fn encrypt_inputs<'a>(
+    paths: impl IntoIterator<Item = &'a Path>,
+    remove: bool,
+) -> Result<(), Box<dyn Error>> {
+    let key = key()?;
+
+    for path in paths {
+        if !path.exists() {
+            println!("skipping {}: not found", path.display());
+            continue;
+        }
+        let plaintext = fs::read(path)?;
+        let enc_path = encrypted_path(path);
+        // Each file gets a fresh salt and nonce, so an unchanged input keeps
+        // its file to avoid needless diffs.
+        let unchanged = fs::read(&enc_path)
+            .ok()
+            .and_then(|data| key.decrypt(&data).ok())
+            .is_some_and(|old| old == plaintext);
+        if unchanged {
+            println!("unchanged {}", enc_path.display());
+        } else {
+            fs::write(&enc_path, key.encrypt(&plaintext)?)?;
+            println!("encrypted {}", enc_path.display());
+        }
+        if remove {
+            fs::remove_file(path)?;
+        }
+    }
+
+    Ok(())
+}
+
+pub fn decrypt_inputs<'a>(
+    paths: impl IntoIterator<Item = &'a Path>,
+    remove: bool,
+) -> Result<(), Box<dyn Error>> {
+    let key = key()?;
+
+    for path in paths {
+        let enc_path = encrypted_path(path);
+        if !enc_path.exists() {
+            println!("skipping {}: not found", enc_path.display());
+            continue;
+        }
+        let plaintext = key.decrypt(&fs::read(&enc_path)?).map_err(|e| {
+            format!("failed to decrypt {}: {}", enc_path.display(), e)
+        })?;
+        fs::write(path, plaintext)?;
+        if remove {
+            fs::remove_file(&enc_path)?;
+        }
+        println!("decrypted {}", path.display());
+    }
+
+    Ok(())
+}
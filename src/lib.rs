@@ -0,0 +1,23 @@
+mod animation;
+
+mod config;
+
+mod days;
+pub use days::Cli;
+
+// Shared helpers for the puzzles, also for days built as plugins.
+pub mod graph;
+pub mod grid;
+pub mod parse;
+pub mod simulation;
+
+// The types plugin libraries share with the runner.
+pub mod plugin_abi;
+
+mod input;
+
+mod style;
+
+mod trace;
+
+mod util;
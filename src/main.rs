@@ -1,12 +1,8 @@
 use std::error::Error;
 
+use adventofcode_2021::Cli;
 use structopt::StructOpt;
 
-mod days;
-use days::Cli;
-
-mod util;
-
 fn main() -> Result<(), Box<dyn Error>> {
     Cli::from_args().run()
 }
@@ -0,0 +1,333 @@
+use std::{
+    error::Error,
+    fmt::{self, Debug, Display},
+    str::FromStr,
+};
+
+/// An error at a position of the input. Lines and columns count from 1.
+pub struct ParseError {
+    line: usize,
+    column: Option<usize>,
+    message: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.column {
+            Some(column) => {
+                write!(
+                    f,
+                    "line {}, column {}: {}",
+                    self.line, column, self.message
+                )
+            }
+            None => write!(f, "line {}: {}", self.line, self.message),
+        }
+    }
+}
+
+// Errors are reported with `{:?}`, so show the position there too.
+impl Debug for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl Error for ParseError {}
+
+/// A piece of the input that remembers where it starts, so that errors about
+/// it can point there. The position is only worked out for an error.
+#[derive(Clone, Copy, Debug)]
+pub struct Span<'a> {
+    text: &'a str,
+    /// All of the input, which `text` is a slice of.
+    input: &'a str,
+    /// Errors about spans made of whole lines leave out the column.
+    whole_lines: bool,
+}
+
+/// The lines of `text`, without their line endings.
+pub fn lines(text: &str) -> impl Iterator<Item = Span<'_>> {
+    Span::new(text).lines()
+}
+
+/// The groups of lines of `text` separated by blank lines.
+pub fn sections(text: &str) -> impl Iterator<Item = Span<'_>> {
+    let mut lines = lines(text).peekable();
+    std::iter::from_fn(move || {
+        while lines.next_if(|line| line.text.trim().is_empty()).is_some() {}
+        let first = lines.next()?;
+        let mut len = first.text.len();
+        while let Some(line) = lines.next_if(|l| !l.text.trim().is_empty()) {
+            len =
+                line.offset_in(text) + line.text.len() - first.offset_in(text);
+        }
+        let start = first.offset_in(text);
+        Some(Span { text: &text[start..start + len], ..first })
+    })
+}
+
+impl<'a> Span<'a> {
+    /// All of `text`, starting at line 1, column 1.
+    pub fn new(text: &'a str) -> Self {
+        Self { text, input: text, whole_lines: true }
+    }
+
+    #[inline]
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    pub fn error(&self, message: impl Display) -> ParseError {
+        let before = &self.input[..self.offset_in(self.input)];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        ParseError {
+            line: 1 + before.matches('\n').count(),
+            column: (!self.whole_lines)
+                .then(|| 1 + before[line_start..].chars().count()),
+            message: message.to_string(),
+        }
+    }
+
+    /// The span `len` bytes long that starts `offset` bytes into this one.
+    fn sub(&self, offset: usize, len: usize) -> Self {
+        Self {
+            text: &self.text[offset..offset + len],
+            whole_lines: false,
+            ..*self
+        }
+    }
+
+    fn offset_in(&self, text: &str) -> usize {
+        self.text.as_ptr() as usize - text.as_ptr() as usize
+    }
+
+    pub fn lines(self) -> impl Iterator<Item = Span<'a>> {
+        self.text.lines().enumerate().map(move |(i, text)| Span {
+            text,
+            whole_lines: i > 0 || self.whole_lines,
+            ..self
+        })
+    }
+
+    pub fn trim(&self) -> Self {
+        let start = self.text.len() - self.text.trim_start().len();
+        self.sub(start, self.text.trim().len())
+    }
+
+    pub fn strip_prefix(&self, prefix: &str) -> Result<Self, ParseError> {
+        match self.text.strip_prefix(prefix) {
+            Some(rest) => Ok(self.sub(prefix.len(), rest.len())),
+            None => Err(self.error(format!("expected `{}`", prefix))),
+        }
+    }
+
+    pub fn split_once(&self, sep: &str) -> Result<(Self, Self), ParseError> {
+        match self.text.find(sep) {
+            Some(i) => Ok((
+                self.sub(0, i),
+                self.sub(i + sep.len(), self.text.len() - i - sep.len()),
+            )),
+            None => Err(self.error(format!("expected `{}`", sep))),
+        }
+    }
+
+    pub fn split(self, sep: &'a str) -> impl Iterator<Item = Span<'a>> {
+        self.sub_strs(self.text.split(sep))
+    }
+
+    pub fn split_whitespace(self) -> impl Iterator<Item = Span<'a>> {
+        self.sub_strs(self.text.split_whitespace())
+    }
+
+    /// The spans of `pieces`, which must be slices of this span's text.
+    fn sub_strs(
+        self,
+        pieces: impl Iterator<Item = &'a str>,
+    ) -> impl Iterator<Item = Span<'a>> {
+        pieces.map(move |piece| Span {
+            text: piece,
+            whole_lines: false,
+            ..self
+        })
+    }
+
+    /// A rule such as `CH -> B`, split into its two sides.
+    pub fn rule(&self) -> Result<(Self, Self), ParseError> {
+        let (lhs, rhs) = self.split_once("->")?;
+        Ok((lhs.trim(), rhs.trim()))
+    }
+
+    /// A `key=value` pair.
+    pub fn key_value(&self) -> Result<(Self, Self), ParseError> {
+        let (key, value) = self.split_once("=")?;
+        Ok((key.trim(), value.trim()))
+    }
+
+    pub fn parse<T>(&self) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.parse_with(str::parse)
+    }
+
+    pub fn parse_with<T, E: Display>(
+        &self,
+        f: impl FnOnce(&'a str) -> Result<T, E>,
+    ) -> Result<T, ParseError> {
+        f(self.text).map_err(|e| self.error(format!("`{}`: {}", self.text, e)))
+    }
+
+    /// Values separated by `sep`, such as comma-separated integers.
+    pub fn delimited<T>(self, sep: &'a str) -> Result<Vec<T>, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.split(sep).map(|piece| piece.trim().parse()).collect()
+    }
+
+    /// One value per character.
+    pub fn chars<T, E: Display>(
+        &self,
+        mut f: impl FnMut(char) -> Result<T, E>,
+    ) -> Result<Vec<T>, ParseError> {
+        let mut values = Vec::with_capacity(self.text.len());
+        self.try_for_each_char(|c| f(c).map(|value| values.push(value)))?;
+        Ok(values)
+    }
+
+    /// Call `f` with each character, stopping at the first error.
+    pub fn try_for_each_char<E: Display>(
+        &self,
+        mut f: impl FnMut(char) -> Result<(), E>,
+    ) -> Result<(), ParseError> {
+        for (i, c) in self.text.char_indices() {
+            if let Err(e) = f(c) {
+                return Err(self.sub(i, c.len_utf8()).error(e));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message<T>(result: Result<T, ParseError>) -> String {
+        match result {
+            Ok(_) => panic!("expected an error"),
+            Err(e) => e.to_string(),
+        }
+    }
+
+    #[test]
+    fn errors_about_lines_leave_out_the_column() {
+        let line = lines("a\nb\nc").nth(2).unwrap();
+        assert_eq!(line.error("bad").to_string(), "line 3: bad");
+        assert_eq!(Span::new("").error("empty").to_string(), "line 1: empty");
+    }
+
+    #[test]
+    fn columns_count_characters() {
+        let line = lines("first\nhéllo wörld").nth(1).unwrap();
+        let word = line.split_whitespace().nth(1).unwrap();
+        assert_eq!(word.text(), "wörld");
+        assert_eq!(word.error("bad").to_string(), "line 2, column 7: bad");
+    }
+
+    #[test]
+    fn sections_span_their_lines() {
+        let text = "a\nb\n\n\nc\n  \nd\né\n";
+        let sections: Vec<_> = sections(text).collect();
+        let texts: Vec<_> = sections.iter().map(Span::text).collect();
+        assert_eq!(texts, ["a\nb", "c", "d\né"]);
+        assert_eq!(sections[1].error("bad").to_string(), "line 5: bad");
+
+        let line = sections[2].lines().nth(1).unwrap();
+        assert_eq!(
+            message(line.parse::<u8>()),
+            "line 8: `é`: invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn delimited_points_at_the_bad_field() {
+        let line = lines("# ünïcode\n3, 4,x5, 6").nth(1).unwrap();
+        assert_eq!(
+            message(line.delimited::<u8>(",")),
+            "line 2, column 6: `x5`: invalid digit found in string"
+        );
+        let values = Span::new("1,2,3").delimited::<u8>(",").unwrap();
+        assert_eq!(values, [1, 2, 3]);
+    }
+
+    #[test]
+    fn split_keeps_empty_pieces_in_place() {
+        let errors: Vec<_> = Span::new("ä,,b")
+            .split(",")
+            .map(|piece| piece.error("x").to_string())
+            .collect();
+        assert_eq!(
+            errors,
+            [
+                "line 1, column 1: x",
+                "line 1, column 3: x",
+                "line 1, column 4: x"
+            ]
+        );
+    }
+
+    #[test]
+    fn rules_and_key_values_trim_their_sides() {
+        let line = lines("CH -> B\nNN  ->  Ç").nth(1).unwrap();
+        let (lhs, rhs) = line.rule().unwrap();
+        assert_eq!((lhs.text(), rhs.text()), ("NN", "Ç"));
+        assert_eq!(rhs.error("bad").to_string(), "line 2, column 9: bad");
+
+        let line = lines("x=1\nnot a rule").nth(1).unwrap();
+        assert_eq!(message(line.rule()), "line 2: expected `->`");
+
+        let (key, value) = Span::new("ß = 1x").key_value().unwrap();
+        assert_eq!(key.text(), "ß");
+        assert_eq!(
+            message(value.parse::<u8>()),
+            "line 1, column 5: `1x`: invalid digit found in string"
+        );
+        assert_eq!(message(key.key_value()), "line 1, column 1: expected `=`");
+    }
+
+    #[test]
+    fn chars_point_at_the_bad_character() {
+        let line = lines("\n\nab€ç!").nth(2).unwrap();
+        let err = line
+            .try_for_each_char(|c| match c.is_ascii() || c == '€' {
+                true => Ok(()),
+                false => Err(format!("unexpected `{}`", c)),
+            })
+            .unwrap_err();
+        assert_eq!(err.to_string(), "line 3, column 4: unexpected `ç`");
+
+        let digits = Span::new("12").chars(|c| c.to_digit(10).ok_or("no"));
+        assert_eq!(digits.unwrap(), [1, 2]);
+    }
+
+    #[test]
+    fn prefixes_and_separators_report_what_is_missing() {
+        let line = lines("\nfold along y=7").nth(1).unwrap();
+        let rest = line.strip_prefix("fold along ").unwrap();
+        let (axis, value) = rest.split_once("=").unwrap();
+        assert_eq!((axis.text(), value.text()), ("y", "7"));
+        assert_eq!(value.error("bad").to_string(), "line 2, column 14: bad");
+        assert_eq!(
+            message(rest.strip_prefix("x")),
+            "line 2, column 12: expected `x`"
+        );
+        assert_eq!(
+            message(axis.split_once(",")),
+            "line 2, column 12: expected `,`"
+        );
+    }
+}
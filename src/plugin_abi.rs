@@ -0,0 +1,48 @@
+use std::ffi::c_char;
+
+/// Bumped whenever the layout of the types below changes.
+pub const ABI_VERSION: u32 = 1;
+
+/// A plugin is a shared library in the plugins directory that exports
+/// `aoc_plugin_days`, a C function returning a pointer to a static
+/// `PluginDays` listing the days it solves. Every pointer it hands out must
+/// stay valid while the library is loaded, except the answer texts, which
+/// are given back through `free_text`. `examples/plugin_day1.rs` is a
+/// plugin written in Rust.
+#[repr(C)]
+pub struct PluginDays {
+    /// Must be [`ABI_VERSION`].
+    pub abi_version: u32,
+    pub days: *const PluginDaySpec,
+    pub len: usize,
+    /// Releases the text of a [`PluginAnswer`].
+    pub free_text: unsafe extern "C" fn(text: *mut c_char),
+}
+
+#[repr(C)]
+pub struct PluginDaySpec {
+    pub day: u32,
+    /// A NUL-terminated name telling the solution apart from others for
+    /// the same day.
+    pub name: *const c_char,
+    /// Solve `part`, 1 or 2, of the UTF-8 input of `len` bytes.
+    pub solve: unsafe extern "C" fn(
+        part: u32,
+        input: *const u8,
+        len: usize,
+    ) -> PluginAnswer,
+}
+
+// The pointers only point at data that is never written while the library
+// is loaded, so plugins can keep their lists in statics.
+unsafe impl Sync for PluginDays {}
+unsafe impl Sync for PluginDaySpec {}
+
+/// The answer as text, or the error message when `ok` is false. Answers
+/// that parse as integers are integers, and ones with several lines are
+/// grids.
+#[repr(C)]
+pub struct PluginAnswer {
+    pub ok: bool,
+    pub text: *mut c_char,
+}
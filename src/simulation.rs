@@ -0,0 +1,154 @@
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    hash::Hash,
+};
+
+use tracing::{debug, debug_span};
+
+/// Where a simulation started repeating itself: the state before step
+/// `start + len` is the same as the state before step `start`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub len: usize,
+}
+
+pub trait Simulation {
+    /// What a single step reports, e.g. the number of octopus flashes.
+    type Output;
+
+    /// A snapshot of everything that determines the following steps and
+    /// what is asked of the simulation, since [`Simulation::advance`] skips
+    /// the steps between two equal states.
+    type State: Hash + Eq;
+
+    fn step(&mut self) -> Self::Output;
+
+    fn state(&self) -> Self::State;
+
+    /// Take `n` steps and return what each of them reported.
+    fn run(&mut self, n: usize) -> Vec<Self::Output> {
+        let _span = debug_span!("simulate", steps = n).entered();
+        (0..n).map(|_| self.step()).collect()
+    }
+
+    /// Step until `done` accepts the state and output after a step,
+    /// returning how many steps were taken.
+    fn run_until(
+        &mut self,
+        mut done: impl FnMut(&Self, &Self::Output) -> bool,
+    ) -> usize
+    where
+        Self: Sized,
+    {
+        let _span = debug_span!("simulate").entered();
+        let mut n = 1;
+        loop {
+            let output = self.step();
+            if done(self, &output) {
+                return n;
+            }
+            n += 1;
+        }
+    }
+
+    /// Move `n` steps ahead. Once a state repeats, the rest of the steps are
+    /// skipped except for the remainder of the last full cycle.
+    fn advance(&mut self, n: usize) -> Option<Cycle> {
+        let _span = debug_span!("simulate", steps = n).entered();
+        let mut seen = HashMap::new();
+        for i in 0..n {
+            match seen.entry(self.state()) {
+                Entry::Occupied(e) => {
+                    let cycle = Cycle { start: *e.get(), len: i - e.get() };
+                    debug!(cycle.start, cycle.len, "states repeat");
+                    for _ in 0..(n - i) % cycle.len {
+                        self.step();
+                    }
+                    return Some(cycle);
+                }
+                Entry::Vacant(e) => {
+                    e.insert(i);
+                }
+            }
+            self.step();
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts up to `wrap` and starts over from `reset`.
+    struct Counter {
+        value: u32,
+        reset: u32,
+        wrap: u32,
+        steps: usize,
+    }
+
+    impl Counter {
+        fn new(reset: u32, wrap: u32) -> Self {
+            Self { value: 0, reset, wrap, steps: 0 }
+        }
+    }
+
+    impl Simulation for Counter {
+        type Output = u32;
+        type State = u32;
+
+        fn step(&mut self) -> u32 {
+            self.steps += 1;
+            self.value = match self.value + 1 {
+                v if v == self.wrap => self.reset,
+                v => v,
+            };
+            self.value
+        }
+
+        fn state(&self) -> u32 {
+            self.value
+        }
+    }
+
+    #[test]
+    fn run_reports_every_step() {
+        assert_eq!(Counter::new(2, 4).run(5), [1, 2, 3, 2, 3]);
+    }
+
+    #[test]
+    fn run_until_counts_steps() {
+        let mut counter = Counter::new(0, 100);
+        assert_eq!(counter.run_until(|_, &v| v == 7), 7);
+    }
+
+    #[test]
+    fn advance_skips_the_cycle() {
+        // 0 1 2 3 4 | 2 3 4 | 2 3 4 ...
+        let mut counter = Counter::new(2, 5);
+        assert_eq!(counter.advance(1000), Some(Cycle { start: 2, len: 3 }));
+        assert_eq!(counter.value, Counter::new(2, 5).run(1000)[999]);
+        assert!(counter.steps < 10);
+    }
+
+    #[test]
+    fn advance_lands_exactly_on_the_cycle() {
+        // After 2 + 3k steps the counter is back at the start of the cycle.
+        let mut counter = Counter::new(2, 5);
+        assert_eq!(
+            counter.advance(2 + 3 * 50),
+            Some(Cycle { start: 2, len: 3 })
+        );
+        assert_eq!(counter.value, 2);
+        assert_eq!(counter.steps, 5);
+    }
+
+    #[test]
+    fn advance_without_a_cycle_takes_every_step() {
+        let mut counter = Counter::new(0, 100);
+        assert_eq!(counter.advance(50), None);
+        assert_eq!((counter.value, counter.steps), (50, 50));
+    }
+}
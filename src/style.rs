@@ -0,0 +1,189 @@
+use std::{
+    env,
+    error::Error,
+    fmt::{self, Display},
+    io::{self, IsTerminal},
+    str::FromStr,
+    sync::OnceLock,
+};
+
+/// Width used for rules and banners when stdout is not a terminal.
+const DEFAULT_WIDTH: usize = 60;
+
+static COLOR: OnceLock<bool> = OnceLock::new();
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// Color only when stdout is a terminal and `NO_COLOR` is not set.
+    Auto,
+    Always,
+    Never,
+}
+
+impl FromStr for ColorChoice {
+    type Err = Box<dyn Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(Self::Auto),
+            "always" => Ok(Self::Always),
+            "never" => Ok(Self::Never),
+            _ => Err(format!(
+                "invalid color choice: {} (expected auto, always or never)",
+                s
+            )
+            .into()),
+        }
+    }
+}
+
+impl ColorChoice {
+    fn enabled(self) -> bool {
+        match self {
+            Self::Always => true,
+            Self::Never => false,
+            Self::Auto => {
+                env::var_os("NO_COLOR").is_none_or(|v| v.is_empty())
+                    && io::stdout().is_terminal()
+            }
+        }
+    }
+}
+
+/// Decide once whether output is styled. Without a call, styling follows
+/// [`ColorChoice::Auto`].
+pub fn init(choice: ColorChoice) {
+    COLOR.get_or_init(|| choice.enabled());
+    // Keeps the colors crossterm draws itself in line with the choice.
+    #[cfg(feature = "tui")]
+    crossterm::style::force_color_output(enabled());
+}
+
+pub fn enabled() -> bool {
+    *COLOR.get_or_init(|| ColorChoice::Auto.enabled())
+}
+
+/// The width of the terminal, or a fixed width when stdout is not one or,
+/// without the `tui` feature, when its size cannot be asked.
+pub fn width() -> usize {
+    if !io::stdout().is_terminal() {
+        return DEFAULT_WIDTH;
+    }
+    #[cfg(feature = "tui")]
+    if let Ok((cols, _)) = crossterm::terminal::size() {
+        return cols as usize;
+    }
+    DEFAULT_WIDTH
+}
+
+/// The escape that colors the text after it with an RGB color.
+pub fn rgb((r, g, b): (u8, u8, u8)) -> String {
+    format!("\x1b[38;2;{};{};{}m", r, g, b)
+}
+
+/// The escape that ends any styling.
+pub const RESET: &str = "\x1b[0m";
+
+#[derive(Clone, Copy)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Cyan,
+}
+
+impl Color {
+    /// The SGR parameter of the color as a foreground, in its bright
+    /// variant as the 16 basic colors are usually shown.
+    const fn code(self) -> u8 {
+        match self {
+            Self::Red => 91,
+            Self::Green => 92,
+            Self::Yellow => 93,
+            Self::Cyan => 96,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Style {
+    color: Option<Color>,
+    bold: bool,
+    dim: bool,
+}
+
+impl Style {
+    pub const fn plain() -> Self {
+        Self { color: None, bold: false, dim: false }
+    }
+
+    pub const fn color(self, color: Color) -> Self {
+        Self { color: Some(color), ..self }
+    }
+
+    pub const fn bold(self) -> Self {
+        Self { bold: true, ..self }
+    }
+
+    pub const fn dim(self) -> Self {
+        Self { dim: true, ..self }
+    }
+
+    /// `value` wrapped in this style's escapes, when styling is enabled.
+    pub fn paint<T: Display>(self, value: T) -> Painted<T> {
+        Painted { style: self, value }
+    }
+
+    /// The SGR parameters of the style, empty when it is plain.
+    fn codes(self) -> Vec<u8> {
+        let mut codes = Vec::new();
+        if self.bold {
+            codes.push(1);
+        }
+        if self.dim {
+            codes.push(2);
+        }
+        codes.extend(self.color.map(Color::code));
+        codes
+    }
+}
+
+pub struct Painted<T> {
+    style: Style,
+    value: T,
+}
+
+impl<T: Display> Display for Painted<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let codes = self.style.codes();
+        if !enabled() || codes.is_empty() {
+            return self.value.fmt(f);
+        }
+        let codes: Vec<_> = codes.iter().map(u8::to_string).collect();
+        write!(f, "\x1b[{}m", codes.join(";"))?;
+        self.value.fmt(f)?;
+        f.write_str(RESET)
+    }
+}
+
+pub struct Theme {
+    /// The `===[ Day NN ]===` banners of `all`.
+    pub banner: Style,
+    /// Section and table headings.
+    pub heading: Style,
+    pub answer: Style,
+    pub error: Style,
+    /// Timings and other secondary notes.
+    pub muted: Style,
+    /// Cells that stand out in a day's debug output.
+    pub highlight: Style,
+}
+
+pub const THEME: Theme = Theme {
+    banner: Style::plain().color(Color::Cyan).bold(),
+    heading: Style::plain().bold(),
+    answer: Style::plain().color(Color::Green).bold(),
+    error: Style::plain().color(Color::Red).bold(),
+    muted: Style::plain().dim(),
+    highlight: Style::plain().color(Color::Yellow),
+};
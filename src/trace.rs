@@ -0,0 +1,43 @@
+use std::{error::Error, io, path::Path};
+
+use tracing_chrome::{ChromeLayerBuilder, FlushGuard};
+use tracing_subscriber::{
+    filter::LevelFilter, fmt, layer::SubscriberExt, util::SubscriberInitExt,
+    EnvFilter, Layer,
+};
+
+use crate::style;
+
+/// Log to stderr at the level `RUST_LOG` sets, or else at warnings plus one
+/// more level per `-v`. With `chrome_trace`, every span is also written
+/// there as a Chrome trace, which lasts until the returned guard is
+/// dropped; open it in `chrome://tracing` or Perfetto.
+pub fn init(
+    verbosity: u8,
+    chrome_trace: Option<&Path>,
+) -> Result<Option<FlushGuard>, Box<dyn Error>> {
+    let level = match verbosity {
+        0 => LevelFilter::WARN,
+        1 => LevelFilter::INFO,
+        2 => LevelFilter::DEBUG,
+        _ => LevelFilter::TRACE,
+    };
+    let filter =
+        EnvFilter::builder().with_default_directive(level.into()).from_env()?;
+    let log = fmt::layer()
+        .with_writer(io::stderr)
+        .with_ansi(style::enabled())
+        .with_filter(filter);
+
+    let (chrome, guard) = match chrome_trace {
+        Some(path) => {
+            let (layer, guard) =
+                ChromeLayerBuilder::new().file(path).include_args(true).build();
+            (Some(layer), Some(guard))
+        }
+        None => (None, None),
+    };
+
+    tracing_subscriber::registry().with(log).with(chrome).try_init()?;
+    Ok(guard)
+}